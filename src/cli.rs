@@ -0,0 +1,247 @@
+use std::fmt;
+
+pub const USAGE: &str = "Usage: aoc2024 [COMMAND] [OPTIONS]
+
+Commands:
+    run     Run puzzle solvers (default)
+    list    List the available days and parts
+    help    Print this message
+
+Options for `run`:
+    --all           Run every available puzzle (default)
+    --day <N>       Run only day N
+    --part <P>      Run only part P of the selected day (1 or 2)
+    --input <PATH>  Read the selected day's input from PATH (`-` for stdin)
+    --verbose       Print the selected day's answers in full (day 14 draws its picture)
+
+Without `--input`, each day reads `inputs/dayNN.txt` if it exists and falls
+back to the input embedded in the binary.";
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(Selection),
+    List,
+    Help,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Selection {
+    All,
+    Day {
+        day: u8,
+        part: Option<u8>,
+        input: Option<String>,
+        verbose: bool,
+    },
+}
+
+#[derive(Debug, PartialEq)]
+pub enum CliError {
+    UnknownCommand(String),
+    UnknownFlag(String),
+    MissingValue(&'static str),
+    BadNumber { flag: &'static str, value: String },
+    BadPart(u8),
+    PartWithoutDay,
+    InputWithoutDay,
+    VerboseWithoutDay,
+    AllWithDay,
+}
+
+impl fmt::Display for CliError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CliError::UnknownCommand(cmd) => write!(f, "unknown command `{cmd}`"),
+            CliError::UnknownFlag(flag) => write!(f, "unknown option `{flag}`"),
+            CliError::MissingValue(flag) => write!(f, "option `{flag}` needs a value"),
+            CliError::BadNumber { flag, value } => {
+                write!(f, "option `{flag}` expects a number, got `{value}`")
+            }
+            CliError::BadPart(part) => write!(f, "there is no part {part}, only 1 and 2"),
+            CliError::PartWithoutDay => write!(f, "`--part` can only be used with `--day`"),
+            CliError::InputWithoutDay => write!(f, "`--input` can only be used with `--day`"),
+            CliError::VerboseWithoutDay => write!(f, "`--verbose` can only be used with `--day`"),
+            CliError::AllWithDay => write!(f, "`--all` can't be combined with `--day`"),
+        }
+    }
+}
+
+/*
+Parse the command line (without the program name) into a Command.
+
+No arguments at all means "run everything", which is what the binary always
+did before it had a command line.
+ */
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Command, CliError> {
+    let mut args = args.into_iter().peekable();
+
+    // The subcommand is optional. Anything starting with "--" is an option for `run`.
+    let command = match args.peek().map(|s| s.as_str()) {
+        None => return Ok(Command::Run(Selection::All)),
+        Some(arg) if arg.starts_with("--") => "run".to_string(),
+        Some(_) => args.next().unwrap(),
+    };
+
+    match command.as_str() {
+        "run" => parse_run_options(args).map(Command::Run),
+        "list" => Ok(Command::List),
+        "help" | "-h" => Ok(Command::Help),
+        _ => Err(CliError::UnknownCommand(command)),
+    }
+}
+
+fn parse_run_options(mut args: impl Iterator<Item = String>) -> Result<Selection, CliError> {
+    let mut all = false;
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut verbose = false;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--all" => all = true,
+            "--day" => day = Some(parse_number("--day", args.next())?),
+            "--part" => part = Some(parse_number("--part", args.next())?),
+            "--input" => input = Some(args.next().ok_or(CliError::MissingValue("--input"))?),
+            "--verbose" => verbose = true,
+            _ => return Err(CliError::UnknownFlag(arg)),
+        }
+    }
+
+    if let Some(part) = part {
+        if part != 1 && part != 2 {
+            return Err(CliError::BadPart(part));
+        }
+    }
+
+    match (all, day, part, input, verbose) {
+        (true, Some(_), _, _, _) => Err(CliError::AllWithDay),
+        (_, None, Some(_), _, _) => Err(CliError::PartWithoutDay),
+        (_, None, _, Some(_), _) => Err(CliError::InputWithoutDay),
+        (_, None, _, _, true) => Err(CliError::VerboseWithoutDay),
+        (_, Some(day), part, input, verbose) => Ok(Selection::Day {
+            day,
+            part,
+            input,
+            verbose,
+        }),
+        (_, None, None, None, false) => Ok(Selection::All),
+    }
+}
+
+fn parse_number(flag: &'static str, value: Option<String>) -> Result<u8, CliError> {
+    let value = value.ok_or(CliError::MissingValue(flag))?;
+    value
+        .parse::<u8>()
+        .map_err(|_| CliError::BadNumber { flag, value })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn no_args_runs_everything() {
+        assert_eq!(parse_args(args("")), Ok(Command::Run(Selection::All)));
+    }
+
+    #[test]
+    fn run_single_part() {
+        let expected = Command::Run(Selection::Day {
+            day: 8,
+            part: Some(2),
+            input: None,
+            verbose: false,
+        });
+        assert_eq!(parse_args(args("run --day 8 --part 2")), Ok(expected));
+    }
+
+    #[test]
+    fn run_is_implied_by_options() {
+        let expected = Command::Run(Selection::Day {
+            day: 3,
+            part: None,
+            input: None,
+            verbose: false,
+        });
+        assert_eq!(parse_args(args("--day 3")), Ok(expected));
+    }
+
+    #[test]
+    fn run_with_input_file() {
+        let expected = Command::Run(Selection::Day {
+            day: 5,
+            part: None,
+            input: Some("-".to_string()),
+            verbose: false,
+        });
+        assert_eq!(parse_args(args("run --day 5 --input -")), Ok(expected));
+    }
+
+    #[test]
+    fn run_verbose() {
+        let expected = Command::Run(Selection::Day {
+            day: 14,
+            part: Some(2),
+            input: None,
+            verbose: true,
+        });
+        assert_eq!(
+            parse_args(args("run --day 14 --part 2 --verbose")),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn verbose_needs_day() {
+        assert_eq!(
+            parse_args(args("run --verbose")),
+            Err(CliError::VerboseWithoutDay)
+        );
+    }
+
+    #[test]
+    fn input_needs_day() {
+        assert_eq!(
+            parse_args(args("run --input day5.txt")),
+            Err(CliError::InputWithoutDay)
+        );
+    }
+
+    #[test]
+    fn part_needs_day() {
+        assert_eq!(
+            parse_args(args("run --part 1")),
+            Err(CliError::PartWithoutDay)
+        );
+    }
+
+    #[test]
+    fn only_two_parts() {
+        assert_eq!(
+            parse_args(args("run --day 1 --part 3")),
+            Err(CliError::BadPart(3))
+        );
+    }
+
+    #[test]
+    fn bad_day_number() {
+        let expected = CliError::BadNumber {
+            flag: "--day",
+            value: "eight".to_string(),
+        };
+        assert_eq!(parse_args(args("run --day eight")), Err(expected));
+    }
+
+    #[test]
+    fn missing_day_number() {
+        assert_eq!(
+            parse_args(args("run --day")),
+            Err(CliError::MissingValue("--day"))
+        );
+    }
+}
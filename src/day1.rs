@@ -1,60 +1,82 @@
 use std::collections::HashMap;
 
-fn parse_lists(input: &str) -> (Vec<i32>, Vec<i32>) {
-    input
-        .split("\n")
-        .map(|line| -> (i32, i32) {
-            let mut parts = line.split("   ");
-            let first = parts.next().expect("Failed to get number 1 in a line");
-            let second = parts.next().expect("Failed to get number 2 in a line");
+use crate::{
+    error::{parse_number, ParseError, ParseErrorKind, Result},
+    solution::Solution,
+};
 
-            let first_int = first
-                .parse::<i32>()
-                .expect("Failed to parse number 1 in a line");
+fn parse_lists(input: &str) -> Result<(Vec<i32>, Vec<i32>)> {
+    let pairs = input
+        .lines()
+        .enumerate()
+        .map(|(line_idx, line)| -> Result<(i32, i32)> {
+            let mut parts = line.split("   ");
+            // `split()` always yields at least one item, even for an empty line.
+            let first = parts.next().unwrap();
+            let second = parts.next().ok_or_else(|| {
+                ParseError::end_of_line(
+                    Day1::DAY,
+                    line_idx,
+                    line,
+                    ParseErrorKind::MissingField("number 2"),
+                )
+            })?;
 
-            let second_int = second
-                .parse::<i32>()
-                .expect("Failed to parse number 2 in a line");
+            let first_int = parse_number(Day1::DAY, line_idx, line, first)?;
+            let second_int = parse_number(Day1::DAY, line_idx, line, second)?;
 
-            return (first_int, second_int);
+            return Ok((first_int, second_int));
         })
-        .unzip()
+        .collect::<Result<Vec<(i32, i32)>>>()?;
+    Ok(pairs.into_iter().unzip())
 }
 
-pub fn process_d1p1(input: &str) -> i32 {
-    let (mut col1, mut col2): (Vec<_>, Vec<_>) = parse_lists(input);
+pub struct Day1;
 
-    col1.sort();
-    col2.sort();
+impl Solution for Day1 {
+    const DAY: u8 = 1;
 
-    let element_diffs: i32 = col1
-        .iter()
-        .zip(col2.iter())
-        .map(|(left, right)| (left - right).abs())
-        .sum();
-    return element_diffs;
-}
+    type Input = (Vec<i32>, Vec<i32>);
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-pub fn process_d1p2(input: &str) -> i32 {
-    let (col1, col2): (Vec<_>, Vec<_>) = parse_lists(input);
-
-    let frequencies = col2.iter().copied().fold(HashMap::new(), |mut map, val| {
-        map.entry(val).and_modify(|frq| *frq += 1).or_insert(1);
-        map
-    });
-
-    let sum: i32 = col1
-        .into_iter()
-        .map(|value| {
-            if let Some(freq) = frequencies.get(&value) {
-                return value * freq;
-            } else {
-                return 0;
-            }
-        })
-        .sum();
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_lists(input)
+    }
+
+    fn part1(lists: &Self::Input) -> Result<i32> {
+        let (mut col1, mut col2) = lists.clone();
+
+        col1.sort();
+        col2.sort();
+
+        let element_diffs: i32 = col1
+            .iter()
+            .zip(col2.iter())
+            .map(|(left, right)| (left - right).abs())
+            .sum();
+        return Ok(element_diffs);
+    }
+
+    fn part2((col1, col2): &Self::Input) -> Result<i32> {
+        let frequencies = col2.iter().copied().fold(HashMap::new(), |mut map, val| {
+            map.entry(val).and_modify(|frq| *frq += 1).or_insert(1);
+            map
+        });
+
+        let sum: i32 = col1
+            .iter()
+            .map(|value| {
+                if let Some(freq) = frequencies.get(value) {
+                    return value * freq;
+                } else {
+                    return 0;
+                }
+            })
+            .sum();
 
-    return sum;
+        return Ok(sum);
+    }
 }
 
 #[cfg(test)]
@@ -70,13 +92,37 @@ mod day1_tests {
 
     #[test]
     fn run_part1_example() {
-        assert_eq!(process_d1p1(SAMPLE_TEXT), 11);
+        assert_eq!(Day1::solve_part1(SAMPLE_TEXT).unwrap(), 11);
+    }
+
+    #[test]
+    fn run_part1_example_trailing_newline() {
+        let input = format!("{SAMPLE_TEXT}\n");
+        assert_eq!(Day1::solve_part1(&input).unwrap(), 11);
     }
 
     #[test]
     fn run_part2_example() {
-        let result = process_d1p2(SAMPLE_TEXT);
+        let result = Day1::solve_part2(SAMPLE_TEXT).unwrap();
         eprintln!("Got result: {result}");
         assert_eq!(result, 31);
     }
+
+    #[test]
+    fn parse_bad_number() {
+        let error = Day1::parse("3   4\n4   x3").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 1 input, line 2, column 5: expected a number, found `x3`"
+        );
+    }
+
+    #[test]
+    fn parse_missing_number() {
+        let error = Day1::parse("3   4\n4").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 1 input, line 2, column 2: missing number 2"
+        );
+    }
 }
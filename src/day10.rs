@@ -0,0 +1,152 @@
+use std::collections::HashSet;
+
+use crate::{error::Result, geometry::Point, grid::Grid, solution::Solution};
+
+const TRAILHEAD: u8 = 0;
+const SUMMIT: u8 = 9;
+
+/*
+Topographic map of heights 0-9. Some of the puzzle's smaller examples mark
+places nobody can stand with '.', which becomes `None`.
+ */
+pub type TopoMap = Grid<Option<u8>>;
+
+// Neighbours of `p` that are exactly one step higher: the next steps on a trail.
+fn uphill(map: &TopoMap, p: Point) -> impl Iterator<Item = Point> + '_ {
+    let height = map.at(p).copied().flatten();
+    map.neighbours4(p)
+        .filter(move |&next| match (height, map.at(next)) {
+            (Some(h), Some(&Some(next_h))) => next_h == h + 1,
+            _ => false,
+        })
+}
+
+fn trailheads(map: &TopoMap) -> impl Iterator<Item = Point> + '_ {
+    map.points()
+        .filter(|&p| map.at(p) == Some(&Some(TRAILHEAD)))
+}
+
+// Every summit reachable from `start` by walking uphill.
+fn reachable_summits(map: &TopoMap, start: Point) -> HashSet<Point> {
+    let mut summits = HashSet::new();
+    let mut seen = HashSet::new();
+    let mut stack = vec![start];
+    while let Some(p) = stack.pop() {
+        if !seen.insert(p) {
+            continue;
+        }
+        if map.at(p) == Some(&Some(SUMMIT)) {
+            summits.insert(p);
+        }
+        stack.extend(uphill(map, p));
+    }
+    return summits;
+}
+
+/*
+Number of distinct uphill trails from each cell to any summit.
+
+A cell's count is the sum of the counts of the cells one step above it, and a
+summit counts as one trail. Filling the table from the summits downwards means
+every count it needs is already known, so each cell is only visited once.
+ */
+fn trail_counts(map: &TopoMap) -> Grid<u64> {
+    let mut counts = map.map(|_| 0);
+    for height in (TRAILHEAD..=SUMMIT).rev() {
+        for p in map.points().filter(|&p| map.at(p) == Some(&Some(height))) {
+            let count = if height == SUMMIT {
+                1
+            } else {
+                uphill(map, p).map(|next| counts.at(next).unwrap()).sum()
+            };
+            *counts.at_mut(p).unwrap() = count;
+        }
+    }
+    return counts;
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+
+    type Input = TopoMap;
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Grid::parse(Day10::DAY, input, |c| match c {
+            '.' => Some(None),
+            _ => c.to_digit(10).map(|h| Some(h as u8)),
+        })?)
+    }
+
+    // Sum of each trailhead's score: how many summits it can reach.
+    fn part1(map: &Self::Input) -> Result<usize> {
+        let score = trailheads(map)
+            .map(|head| reachable_summits(map, head).len())
+            .sum();
+        return Ok(score);
+    }
+
+    // Sum of each trailhead's rating: how many distinct trails start there.
+    fn part2(map: &Self::Input) -> Result<u64> {
+        let counts = trail_counts(map);
+        let rating = trailheads(map).map(|head| counts.at(head).unwrap()).sum();
+        return Ok(rating);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_TEXT: &str = "89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732";
+
+    #[test]
+    fn run_part1_example() {
+        let expected = 36;
+        let result = Day10::solve_part1(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part2_example() {
+        let expected = 81;
+        let result = Day10::solve_part2(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn impassable_cells() {
+        // One trailhead, two summits
+        let input = "...0...
+...1...
+...2...
+6543456
+7.....7
+8.....8
+9.....9";
+        assert_eq!(Day10::solve_part1(input).unwrap(), 2);
+        assert_eq!(Day10::solve_part2(input).unwrap(), 2);
+    }
+
+    #[test]
+    fn rating_counts_branches() {
+        let input = ".....0.
+..4321.
+..5..2.
+..6543.
+..7..4.
+..8765.
+..9....";
+        assert_eq!(Day10::solve_part2(input).unwrap(), 3);
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::{parse_number, Error, Result},
+    solution::Solution,
+};
+
+/*
+The line of stones, kept as how many stones carry each number.
+
+Stones never affect their neighbours and the puzzle only asks how many there
+are, so their order can be dropped. Lots of stones end up with the same number
+(0 -> 1 -> 2024 -> 20, 24 -> 2, 0, 2, 4 -> ...), and each distinct number only
+has to be worked out once per blink no matter how many stones carry it.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stones {
+    counts: HashMap<u64, u64>,
+}
+
+impl Stones {
+    fn parse(input: &str) -> Result<Self> {
+        let mut counts = HashMap::new();
+        for (line_idx, line) in input.lines().enumerate() {
+            for token in line.split_whitespace() {
+                let number = parse_number(Day11::DAY, line_idx, line, token)?;
+                *counts.entry(number).or_insert(0) += 1;
+            }
+        }
+        Ok(Self { counts })
+    }
+
+    // The stones after blinking `n` times, `None` if a number outgrows a u64.
+    pub fn blink(&self, n: usize) -> Option<Self> {
+        let mut stones = self.clone();
+        for _ in 0..n {
+            stones = stones.blink_once()?;
+        }
+        return Some(stones);
+    }
+
+    fn blink_once(&self) -> Option<Self> {
+        let mut counts = HashMap::with_capacity(self.counts.len() * 2);
+        for (&number, &count) in &self.counts {
+            let (first, second) = change(number)?;
+            *counts.entry(first).or_insert(0) += count;
+            if let Some(second) = second {
+                *counts.entry(second).or_insert(0) += count;
+            }
+        }
+        Some(Self { counts })
+    }
+
+    // Total number of stones
+    pub fn count(&self) -> u64 {
+        self.counts.values().sum()
+    }
+}
+
+/*
+What one stone turns into on a blink. The first rule that applies wins:
+1. 0 becomes 1.
+2. An even number of digits splits into two stones, the left and right halves
+   of the digits. Leading zeros are dropped (1000 -> 10, 0).
+3. Anything else is multiplied by 2024.
+
+`None` if the multiplication doesn't fit in a u64.
+ */
+fn change(number: u64) -> Option<(u64, Option<u64>)> {
+    if number == 0 {
+        return Some((1, None));
+    }
+    let digits = number.ilog10() + 1;
+    if digits.is_multiple_of(2) {
+        let half = 10u64.pow(digits / 2);
+        return Some((number / half, Some(number % half)));
+    }
+    return Some((number.checked_mul(2024)?, None));
+}
+
+fn count_after(stones: &Stones, blinks: usize, part: u8) -> Result<u64> {
+    let stones = stones.blink(blinks).ok_or_else(|| Error::NoSolution {
+        day: Day11::DAY,
+        part,
+        reason: "a stone's number grew too big for 64 bits".to_string(),
+    })?;
+    return Ok(stones.count());
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+
+    type Input = Stones;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Stones::parse(input)
+    }
+
+    fn part1(stones: &Self::Input) -> Result<u64> {
+        count_after(stones, 25, 1)
+    }
+
+    fn part2(stones: &Self::Input) -> Result<u64> {
+        count_after(stones, 75, 2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_TEXT: &str = "125 17";
+
+    #[test]
+    fn run_part1_example() {
+        let expected = 55312;
+        let result = Day11::solve_part1(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part2_example() {
+        let expected = 65601038650482;
+        let result = Day11::solve_part2(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn blink_counts() {
+        // The stone counts after each of the first 6 blinks, from the puzzle text
+        let stones = Day11::parse(SAMPLE_TEXT).unwrap();
+        let counts: Vec<u64> = (0..=6).map(|n| stones.blink(n).unwrap().count()).collect();
+        assert_eq!(counts, vec![2, 3, 4, 5, 9, 13, 22]);
+    }
+
+    #[test]
+    fn stone_rules() {
+        assert_eq!(change(0), Some((1, None)));
+        assert_eq!(change(1000), Some((10, Some(0))));
+        assert_eq!(change(99), Some((9, Some(9))));
+        assert_eq!(change(999), Some((2021976, None)));
+    }
+
+    #[test]
+    fn oversized_stone_is_reported() {
+        assert_eq!(change(10000000000000000), None);
+        let error = Day11::solve_part1("10000000000000000").unwrap_err();
+        assert!(matches!(
+            error,
+            Error::NoSolution {
+                day: 11,
+                part: 1,
+                ..
+            }
+        ));
+    }
+}
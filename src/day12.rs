@@ -0,0 +1,196 @@
+use std::collections::HashSet;
+
+use crate::{
+    error::Result,
+    geometry::{Dir4, Point},
+    grid::Grid,
+    solution::Solution,
+};
+
+// One connected patch of garden plots growing the same plant.
+#[derive(Debug)]
+struct Region {
+    plots: HashSet<Point>,
+}
+
+impl Region {
+    fn area(&self) -> usize {
+        self.plots.len()
+    }
+
+    // Every plot edge that doesn't touch another plot of this region needs fence.
+    fn perimeter(&self) -> usize {
+        self.plots
+            .iter()
+            .flat_map(|plot| plot.neighbours4())
+            .filter(|neighbour| !self.plots.contains(neighbour))
+            .count()
+    }
+
+    /*
+    A polygon has as many sides as it has corners, and corners are easier to
+    find: each one belongs to a single plot. For every plot, look at each pair
+    of adjacent directions (e.g.: up and right):
+
+        outer corner        inner corner
+            . .                 X .
+            X .                 X X
+
+    Outer: neither neighbour is in the region.
+    Inner: both neighbours are, but the diagonal between them isn't.
+     */
+    fn sides(&self) -> usize {
+        let mut corners = 0;
+        for &plot in &self.plots {
+            for dir in Dir4::ALL {
+                let a = plot + dir.as_vector();
+                let b = plot + dir.counter_clockwise().as_vector();
+                let diagonal = a + dir.counter_clockwise().as_vector();
+                let has_a = self.plots.contains(&a);
+                let has_b = self.plots.contains(&b);
+                let outer = !has_a && !has_b;
+                let inner = has_a && has_b && !self.plots.contains(&diagonal);
+                if outer || inner {
+                    corners += 1;
+                }
+            }
+        }
+        return corners;
+    }
+}
+
+// Flood fill every region on the map, each plot ending up in exactly one.
+fn regions(garden: &Grid<char>) -> Vec<Region> {
+    let mut seen = HashSet::new();
+    let mut regions = Vec::new();
+    for start in garden.points() {
+        if seen.contains(&start) {
+            continue;
+        }
+        let plant = garden.at(start);
+        let mut plots = HashSet::new();
+        let mut stack = vec![start];
+        while let Some(p) = stack.pop() {
+            if !seen.insert(p) {
+                continue;
+            }
+            plots.insert(p);
+            stack.extend(
+                garden
+                    .neighbours4(p)
+                    .filter(|&n| garden.at(n) == plant && !seen.contains(&n)),
+            );
+        }
+        regions.push(Region { plots });
+    }
+    return regions;
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+
+    type Input = Grid<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Grid::parse(Day12::DAY, input, |c| {
+            c.is_ascii_uppercase().then_some(c)
+        })?)
+    }
+
+    // Fence price: area * perimeter
+    fn part1(garden: &Self::Input) -> Result<usize> {
+        let price = regions(garden)
+            .iter()
+            .map(|region| region.area() * region.perimeter())
+            .sum();
+        return Ok(price);
+    }
+
+    // Bulk discount price: area * number of sides
+    fn part2(garden: &Self::Input) -> Result<usize> {
+        let price = regions(garden)
+            .iter()
+            .map(|region| region.area() * region.sides())
+            .sum();
+        return Ok(price);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_TEXT: &str = "RRRRIICCFF
+RRRRIICCCF
+VVRRRCCFFF
+VVRCCCJFFF
+VVVVCJJCFE
+VVIVCCJJEE
+VVIIICJJEE
+MIIIIIJJEE
+MIIISIJEEE
+MMMISSJEEE";
+
+    const SMALL_TEXT: &str = "AAAA
+BBCD
+BBCC
+EEEC";
+
+    #[test]
+    fn run_part1_example() {
+        let expected = 1930;
+        let result = Day12::solve_part1(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part1_small_example() {
+        let expected = 140;
+        let result = Day12::solve_part1(SMALL_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part2_example() {
+        let expected = 1206;
+        let result = Day12::solve_part2(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part2_small_example() {
+        let expected = 80;
+        let result = Day12::solve_part2(SMALL_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn sides_of_e_shape() {
+        let input = "EEEEE
+EXXXX
+EEEEE
+EXXXX
+EEEEE";
+        let garden = Day12::parse(input).unwrap();
+        let regions = regions(&garden);
+        let e = regions.iter().find(|r| r.area() == 17).unwrap();
+        assert_eq!(e.sides(), 12);
+        assert_eq!(Day12::solve_part2(input).unwrap(), 236);
+    }
+
+    #[test]
+    fn enclosed_region_sides() {
+        // A's sides include the edges around both B holes
+        let input = "AAAAAA
+AAABBA
+AAABBA
+ABBAAA
+ABBAAA
+AAAAAA";
+        assert_eq!(Day12::solve_part2(input).unwrap(), 368);
+    }
+}
@@ -0,0 +1,357 @@
+use crate::{
+    error::{parse_number, ParseError, ParseErrorKind, Result},
+    solution::Solution,
+};
+
+// Part 2's prizes are this much further away on both axes.
+const PRIZE_OFFSET: i64 = 10_000_000_000_000;
+
+const COST_A: i64 = 3;
+const COST_B: i64 = 1;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Pair {
+    x: i64,
+    y: i64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Machine {
+    a: Pair,
+    b: Pair,
+    prize: Pair,
+}
+
+impl Machine {
+    /*
+    Find the number of A and B presses that land the claw on the prize:
+
+        a * A.x + b * B.x = prize.x
+        a * A.y + b * B.y = prize.y
+
+    Two equations, two unknowns, so Cramer's rule gives the only answer. It's
+    only a real answer if both counts come out as whole, non-negative numbers.
+    `None` means this prize can't be won.
+
+    Parallel buttons make Cramer's rule divide by 0, and are handled by
+    `parallel_presses` instead.
+     */
+    fn presses(&self) -> Option<(i64, i64)> {
+        let Machine { a, b, prize } = *self;
+        let det = a.x * b.y - a.y * b.x;
+        if det == 0 {
+            return self.parallel_presses();
+        }
+        let a_num = prize.x * b.y - prize.y * b.x;
+        let b_num = a.x * prize.y - a.y * prize.x;
+        if a_num % det != 0 || b_num % det != 0 {
+            return None;
+        }
+        let (a_presses, b_presses) = (a_num / det, b_num / det);
+        if a_presses < 0 || b_presses < 0 {
+            return None;
+        }
+        return Some((a_presses, b_presses));
+    }
+
+    /*
+    A and B move the claw along the same line. The prize can only be won if it
+    is on that line too, and then there may be many ways to reach it, so this
+    picks the cheapest. Along the line only one axis matters: whichever one
+    the buttons actually move on.
+     */
+    fn parallel_presses(&self) -> Option<(i64, i64)> {
+        let Machine { a, b, prize } = *self;
+        let zero = Pair { x: 0, y: 0 };
+        if a == zero && b == zero {
+            // Neither button moves the claw, so it stays where it started
+            return (prize == zero).then_some((0, 0));
+        }
+        let direction = if a != zero { a } else { b };
+        if direction.x * prize.y != direction.y * prize.x {
+            return None;
+        }
+        if a.x != 0 || b.x != 0 {
+            cheapest_on_line(a.x, b.x, prize.x)
+        } else {
+            cheapest_on_line(a.y, b.y, prize.y)
+        }
+    }
+
+    fn moved_prize(&self, offset: i64) -> Self {
+        let prize = Pair {
+            x: self.prize.x + offset,
+            y: self.prize.y + offset,
+        };
+        Self { prize, ..*self }
+    }
+}
+
+/*
+The cheapest non-negative `a` and `b` with `a * step_a + b * step_b = target`.
+
+Every whole-number answer is one answer from the extended Euclidean algorithm,
+shifted along by some k:
+
+    a = a0 + k * step_b / g
+    b = b0 - k * step_a / g
+
+The token cost changes linearly with k, so the cheapest answer is at one end
+of the range of k that keeps both press counts non-negative.
+ */
+fn cheapest_on_line(step_a: i64, step_b: i64, target: i64) -> Option<(i64, i64)> {
+    let (g, x, y) = extended_gcd(step_a, step_b);
+    if g == 0 {
+        // Neither button moves the claw at all
+        return (target == 0).then_some((0, 0));
+    }
+    if target % g != 0 {
+        return None;
+    }
+    let (a0, b0) = (x * (target / g), y * (target / g));
+    let (da, db) = (step_b / g, -step_a / g);
+
+    let mut lowest: Option<i64> = None;
+    let mut highest: Option<i64> = None;
+    for (start, step) in [(a0, da), (b0, db)] {
+        match step.signum() {
+            1 => {
+                let bound = (-start).div_euclid(step) + ((-start).rem_euclid(step) != 0) as i64;
+                lowest = Some(lowest.map_or(bound, |k| k.max(bound)));
+            }
+            -1 => {
+                let bound = start.div_euclid(-step);
+                highest = Some(highest.map_or(bound, |k| k.min(bound)));
+            }
+            _ if start < 0 => return None,
+            _ => {}
+        }
+    }
+    if let (Some(lo), Some(hi)) = (lowest, highest) {
+        if lo > hi {
+            return None;
+        }
+    }
+
+    let slope = da * COST_A + db * COST_B;
+    let k = match slope.signum() {
+        1 => lowest?,
+        -1 => highest?,
+        _ => lowest.or(highest).unwrap_or(0),
+    };
+    return Some((a0 + k * da, b0 + k * db));
+}
+
+// (g, x, y) with `a * x + b * y = g`, where g is the non-negative gcd of a and b.
+fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if b == 0 {
+        return if a < 0 { (-a, -1, 0) } else { (a, 1, 0) };
+    }
+    let (g, x, y) = extended_gcd(b, a % b);
+    return (g, y, x - (a / b) * y);
+}
+
+// Tokens needed to win every prize that can be won.
+fn total_cost(machines: impl Iterator<Item = Machine>) -> i64 {
+    machines
+        .filter_map(|machine| machine.presses())
+        .map(|(a, b)| a * COST_A + b * COST_B)
+        .sum()
+}
+
+/*
+Parse one "<label>: X<sep>12, Y<sep>34" line. `sep` is '+' for buttons and
+'=' for the prize.
+ */
+fn parse_pair(
+    line_idx: usize,
+    line: &str,
+    label: &'static str,
+    sep: char,
+) -> std::result::Result<Pair, ParseError> {
+    let missing = |kind| ParseError::end_of_line(Day13::DAY, line_idx, line, kind);
+    let fields = line
+        .strip_prefix(label)
+        .and_then(|rest| rest.strip_prefix(": "))
+        .ok_or_else(|| {
+            ParseError::in_line(
+                Day13::DAY,
+                line_idx,
+                line,
+                line,
+                ParseErrorKind::MissingField(label),
+            )
+        })?;
+    let (x, y) = fields
+        .split_once(", ")
+        .ok_or_else(|| missing(ParseErrorKind::MissingField("Y")))?;
+    let number = |field: &str, axis: char| {
+        let digits = field
+            .strip_prefix(axis)
+            .and_then(|rest| rest.strip_prefix(sep))
+            .unwrap_or(field);
+        parse_number(Day13::DAY, line_idx, line, digits)
+    };
+    Ok(Pair {
+        x: number(x, 'X')?,
+        y: number(y, 'Y')?,
+    })
+}
+
+/*
+Machines are blocks of three lines, separated by blank lines:
+
+    Button A: X+94, Y+34
+    Button B: X+22, Y+67
+    Prize: X=8400, Y=5400
+ */
+fn parse_machines(input: &str) -> Result<Vec<Machine>> {
+    let mut machines = Vec::new();
+    let mut lines = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty());
+    while let Some((a_idx, a_line)) = lines.next() {
+        let a = parse_pair(a_idx, a_line, "Button A", '+')?;
+        let (b_idx, b_line) = lines.next().ok_or_else(|| {
+            let kind = ParseErrorKind::MissingSection("a `Button B` line");
+            ParseError::end_of_line(Day13::DAY, a_idx, a_line, kind)
+        })?;
+        let b = parse_pair(b_idx, b_line, "Button B", '+')?;
+        let (prize_idx, prize_line) = lines.next().ok_or_else(|| {
+            let kind = ParseErrorKind::MissingSection("a `Prize` line");
+            ParseError::end_of_line(Day13::DAY, b_idx, b_line, kind)
+        })?;
+        let prize = parse_pair(prize_idx, prize_line, "Prize", '=')?;
+        machines.push(Machine { a, b, prize });
+    }
+    return Ok(machines);
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+
+    type Input = Vec<Machine>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_machines(input)
+    }
+
+    fn part1(machines: &Self::Input) -> Result<i64> {
+        return Ok(total_cost(machines.iter().copied()));
+    }
+
+    fn part2(machines: &Self::Input) -> Result<i64> {
+        let moved = machines.iter().map(|m| m.moved_prize(PRIZE_OFFSET));
+        return Ok(total_cost(moved));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_TEXT: &str = "Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279";
+
+    #[test]
+    fn run_part1_example() {
+        let expected = 480;
+        let result = Day13::solve_part1(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part2_example() {
+        // The puzzle doesn't give a total, only that the 2nd and 4th prizes can be won
+        let expected = 875318608908;
+        let result = Day13::solve_part2(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn winnable_prizes_after_offset() {
+        let machines = Day13::parse(SAMPLE_TEXT).unwrap();
+        let winnable: Vec<bool> = machines
+            .iter()
+            .map(|m| m.moved_prize(PRIZE_OFFSET).presses().is_some())
+            .collect();
+        assert_eq!(winnable, vec![false, true, false, true]);
+    }
+
+    #[test]
+    fn negative_presses_are_rejected() {
+        // Exactly solvable, but only by pressing A -1 times
+        let machine = Machine {
+            a: Pair { x: 1, y: 0 },
+            b: Pair { x: 0, y: 1 },
+            prize: Pair { x: -1, y: 5 },
+        };
+        assert_eq!(machine.presses(), None);
+    }
+
+    #[test]
+    fn parallel_buttons_pick_the_cheapest() {
+        let machine = |a: (i64, i64), b: (i64, i64), prize: (i64, i64)| Machine {
+            a: Pair { x: a.0, y: a.1 },
+            b: Pair { x: b.0, y: b.1 },
+            prize: Pair {
+                x: prize.0,
+                y: prize.1,
+            },
+        };
+        assert_eq!(machine((1, 2), (2, 4), (3, 6)).presses(), Some((1, 1)));
+        // Four B presses cost less than two A presses
+        assert_eq!(machine((2, 2), (1, 1), (4, 4)).presses(), Some((0, 4)));
+        // Two A presses are cheaper than eight B presses
+        assert_eq!(machine((4, 0), (1, 0), (8, 0)).presses(), Some((2, 0)));
+        assert_eq!(machine((4, 4), (6, 6), (14, 14)).presses(), Some((2, 1)));
+        // Off the line, or not a whole number of steps along it
+        assert_eq!(machine((1, 2), (2, 4), (3, 5)).presses(), None);
+        assert_eq!(machine((2, 4), (4, 8), (3, 6)).presses(), None);
+        // Buttons that don't move the claw only win a prize at the start
+        assert_eq!(machine((0, 0), (0, 0), (5, 0)).presses(), None);
+        assert_eq!(machine((0, 0), (0, 0), (0, 0)).presses(), Some((0, 0)));
+        // Other machines still count
+        let machines = [
+            machine((1, 2), (2, 4), (3, 6)),
+            machine((1, 0), (0, 1), (2, 3)),
+        ];
+        assert_eq!(total_cost(machines.into_iter()), 4 + 9);
+    }
+
+    #[test]
+    fn bad_number_is_located() {
+        let error = Day13::parse("Button A: X+94, Y+3x4").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 13 input, line 1, column 19: expected a number, found `3x4`"
+        );
+    }
+
+    #[test]
+    fn missing_prize_is_reported() {
+        let error = Day13::parse("Button A: X+94, Y+34\nButton B: X+22, Y+67").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 13 input, line 2, column 21: missing a `Prize` line"
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use std::fmt::{self, Display};
+
+use crate::{
+    error::{parse_number, Error, ParseError, ParseErrorKind, Result},
+    geometry::{Point, Vector},
+    grid::Grid,
+    solution::Solution,
+};
+
+// The area the robots patrol. They wrap around at the edges.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Board {
+    pub width: isize,
+    pub height: isize,
+}
+
+// The real puzzle's board
+pub const REAL_BOARD: Board = Board {
+    width: 101,
+    height: 103,
+};
+
+const PART1_SECONDS: usize = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Robot {
+    pos: Point,
+    vel: Vector,
+}
+
+impl Robot {
+    // Where the robot is after `seconds`, wrapping around the board.
+    fn position_at(&self, board: Board, seconds: usize) -> Point {
+        let moved = self.pos + self.vel * seconds as isize;
+        Point::new(
+            moved.x.rem_euclid(board.width),
+            moved.y.rem_euclid(board.height),
+        )
+    }
+}
+
+fn parse_robots(input: &str) -> Result<Vec<Robot>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(line_idx, line)| -> Result<Robot> {
+            // "p=0,4 v=3,-3"
+            let missing = |field| {
+                ParseError::end_of_line(
+                    Day14::DAY,
+                    line_idx,
+                    line,
+                    ParseErrorKind::MissingField(field),
+                )
+            };
+            let (p, v) = line
+                .split_once(' ')
+                .ok_or_else(|| missing("the velocity `v=`"))?;
+            let pair = |text: &str, prefix: &str, field| -> Result<(isize, isize)> {
+                let text = text.strip_prefix(prefix).unwrap_or(text);
+                let (x, y) = text.split_once(',').ok_or_else(|| missing(field))?;
+                let x = parse_number(Day14::DAY, line_idx, line, x)?;
+                let y = parse_number(Day14::DAY, line_idx, line, y)?;
+                Ok((x, y))
+            };
+            return Ok(Robot {
+                pos: pair(p, "p=", "position y")?.into(),
+                vel: pair(v, "v=", "velocity y")?.into(),
+            });
+        })
+        .collect()
+}
+
+/*
+Count the robots in each quadrant after `seconds` and multiply the counts.
+Robots exactly on the middle row or column don't belong to any quadrant.
+ */
+pub fn safety_factor(robots: &[Robot], board: Board, seconds: usize) -> usize {
+    let (mid_x, mid_y) = (board.width / 2, board.height / 2);
+    let mut quadrants = [0; 4];
+    for robot in robots {
+        let p = robot.position_at(board, seconds);
+        if p.x == mid_x || p.y == mid_y {
+            continue;
+        }
+        let idx = (p.x > mid_x) as usize + 2 * (p.y > mid_y) as usize;
+        quadrants[idx] += 1;
+    }
+    return quadrants.iter().product();
+}
+
+fn variance(values: impl Iterator<Item = isize> + Clone) -> f64 {
+    let count = values.clone().count() as f64;
+    let mean = values.clone().sum::<isize>() as f64 / count;
+    values.map(|v| (v as f64 - mean).powi(2)).sum::<f64>() / count
+}
+
+/*
+Find the first second the robots bunch up into a picture.
+
+The x coordinates repeat every `width` seconds and the y coordinates every
+`height` seconds, independently of each other. So look for the second (within
+one cycle) where the x coordinates are the least spread out, and separately
+for y. When the robots form a picture both happen at once, and the Chinese
+remainder theorem finds the second that matches both.
+ */
+pub fn find_picture(robots: &[Robot], board: Board) -> Option<usize> {
+    let tightest = |period: isize, axis: fn(Point) -> isize| {
+        (0..period as usize).min_by(|&a, &b| {
+            let spread = |t| variance(robots.iter().map(|r| axis(r.position_at(board, t))));
+            spread(a).total_cmp(&spread(b))
+        })
+    };
+    let x_time = tightest(board.width, |p| p.x)?;
+    let y_time = tightest(board.height, |p| p.y)?;
+    // Step through the seconds that have the tight x until one has the tight y
+    return (0..board.height as usize)
+        .map(|k| x_time + k * board.width as usize)
+        .find(|t| t % board.height as usize == y_time);
+}
+
+// Draw the board at `seconds`: '#' wherever there's at least one robot.
+pub fn render(robots: &[Robot], board: Board, seconds: usize) -> Grid<char> {
+    let mut picture = Grid::filled(board.width, board.height, '.');
+    for robot in robots {
+        *picture.at_mut(robot.position_at(board, seconds)).unwrap() = '#';
+    }
+    return picture;
+}
+
+/*
+Part 2's answer. It prints as the number of seconds, and the alternate form
+(`{:#}`, which `run --verbose` uses) adds the picture underneath to
+double-check it by eye.
+ */
+#[derive(Debug)]
+pub struct Picture {
+    seconds: usize,
+    frame: Grid<char>,
+}
+
+impl Display for Picture {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.seconds)?;
+        if f.alternate() {
+            write!(f, "\n{}", self.frame)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+
+    type Input = Vec<Robot>;
+    type Answer1 = usize;
+    type Answer2 = Picture;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_robots(input)
+    }
+
+    fn part1(robots: &Self::Input) -> Result<usize> {
+        return Ok(safety_factor(robots, REAL_BOARD, PART1_SECONDS));
+    }
+
+    fn part2(robots: &Self::Input) -> Result<Picture> {
+        let seconds = find_picture(robots, REAL_BOARD).ok_or_else(|| Error::NoSolution {
+            day: Day14::DAY,
+            part: 2,
+            reason: "the robots never line up into a picture".to_string(),
+        })?;
+        let frame = render(robots, REAL_BOARD, seconds);
+        return Ok(Picture { seconds, frame });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // The board used by the puzzle's example
+    const EXAMPLE_BOARD: Board = Board {
+        width: 11,
+        height: 7,
+    };
+
+    const SAMPLE_TEXT: &str = "p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3";
+
+    #[test]
+    fn run_part1_example() {
+        let expected = 12;
+        let robots = Day14::parse(SAMPLE_TEXT).unwrap();
+        let result = safety_factor(&robots, EXAMPLE_BOARD, PART1_SECONDS);
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn example_after_100_seconds() {
+        let robots = Day14::parse(SAMPLE_TEXT).unwrap();
+        // The puzzle's picture, with robot counts flattened to '#'
+        let expected = "......#..#.
+...........
+#..........
+.##........
+.....#.....
+...##......
+.#....#....";
+        let frame = render(&robots, EXAMPLE_BOARD, PART1_SECONDS);
+        assert_eq!(frame.to_string(), expected);
+    }
+
+    #[test]
+    fn single_robot_wraps() {
+        let robots = Day14::parse("p=2,4 v=2,-3").unwrap();
+        let positions: Vec<Point> = (0..=5)
+            .map(|t| robots[0].position_at(EXAMPLE_BOARD, t))
+            .collect();
+        let expected: Vec<Point> = [(2, 4), (4, 1), (6, 5), (8, 2), (10, 6), (1, 3)]
+            .into_iter()
+            .map(Point::from)
+            .collect();
+        assert_eq!(positions, expected);
+    }
+
+    #[test]
+    fn find_hidden_picture() {
+        // Robots that form a filled square at second 4321, on the real board
+        let target = 4321;
+        let mut robots = Vec::new();
+        for (idx, (x, y)) in (40..60)
+            .flat_map(|x| (40..60).map(move |y| (x, y)))
+            .enumerate()
+        {
+            let idx = idx as isize;
+            let vel = Vector::new(idx % 13 - 6, idx % 17 - 8);
+            let pos = Point::new(x, y) - vel * target;
+            let start = Point::new(
+                pos.x.rem_euclid(REAL_BOARD.width),
+                pos.y.rem_euclid(REAL_BOARD.height),
+            );
+            robots.push(Robot { pos: start, vel });
+        }
+        let picture = Day14::part2(&robots).unwrap();
+        assert_eq!(picture.seconds, target as usize);
+        assert_eq!(picture.to_string(), "4321");
+        assert!(format!("{picture:#}").contains(&"#".repeat(20)));
+    }
+
+    #[test]
+    fn missing_velocity_is_reported() {
+        let error = Day14::parse("p=0,4").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 14 input, line 1, column 6: missing the velocity `v=`"
+        );
+    }
+}
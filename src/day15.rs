@@ -0,0 +1,372 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display},
+};
+
+use crate::{
+    error::{ParseError, ParseErrorKind, Result},
+    geometry::{Dir4, Point},
+    grid::Grid,
+    solution::Solution,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tile {
+    Wall,
+    Empty,
+    Robot,
+    // A box one cell wide
+    Box,
+    // The two halves of a box two cells wide
+    BoxLeft,
+    BoxRight,
+}
+
+impl Tile {
+    fn from_char(c: char) -> Option<Self> {
+        match c {
+            '#' => Some(Tile::Wall),
+            '.' => Some(Tile::Empty),
+            '@' => Some(Tile::Robot),
+            'O' => Some(Tile::Box),
+            '[' => Some(Tile::BoxLeft),
+            ']' => Some(Tile::BoxRight),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let c = match self {
+            Tile::Wall => '#',
+            Tile::Empty => '.',
+            Tile::Robot => '@',
+            Tile::Box => 'O',
+            Tile::BoxLeft => '[',
+            Tile::BoxRight => ']',
+        };
+        write!(f, "{c}")
+    }
+}
+
+/*
+The warehouse floor, with the robot on it. The robot is kept on the grid like
+everything else, and its position is remembered so it needn't be searched for.
+
+Prints the same way the puzzle draws it.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Warehouse {
+    floor: Grid<Tile>,
+    robot: Point,
+}
+
+impl Warehouse {
+    fn parse(text: &str) -> Result<Self> {
+        let floor = Grid::parse(Day15::DAY, text, Tile::from_char)?;
+        let robot = floor
+            .position(|&tile| tile == Tile::Robot)
+            .ok_or_else(|| {
+                let kind = ParseErrorKind::MissingSection("the robot `@`");
+                ParseError::end_of_line(Day15::DAY, 0, "", kind)
+            })?
+            .into();
+        Ok(Self { floor, robot })
+    }
+
+    /*
+    Part 2's warehouse: everything except the robot is twice as wide.
+    Walls and floor are doubled, 'O' becomes "[]" and '@' becomes "@.".
+     */
+    fn widen(&self) -> Self {
+        let mut floor = Grid::filled(self.floor.width() * 2, self.floor.height(), Tile::Empty);
+        for (x, y) in self.floor.coords() {
+            let (left, right) = match self.floor.get(x, y).unwrap() {
+                Tile::Wall => (Tile::Wall, Tile::Wall),
+                Tile::Empty => (Tile::Empty, Tile::Empty),
+                Tile::Robot => (Tile::Robot, Tile::Empty),
+                Tile::Box | Tile::BoxLeft | Tile::BoxRight => (Tile::BoxLeft, Tile::BoxRight),
+            };
+            *floor.get_mut(x * 2, y).unwrap() = left;
+            *floor.get_mut(x * 2 + 1, y).unwrap() = right;
+        }
+        let robot = Point::new(self.robot.x * 2, self.robot.y);
+        Self { floor, robot }
+    }
+
+    /*
+    Try to move the robot one step, pushing whatever is in the way.
+
+    Everything that would have to move is gathered first, spreading outward from
+    the robot: each thing pushes whatever is in front of it, and pushing half of
+    a wide box up or down drags the other half along too. If any of them would
+    hit a wall, nothing moves at all.
+     */
+    pub fn step(&mut self, dir: Dir4) {
+        let delta = dir.as_vector();
+        let vertical = matches!(dir, Dir4::Up | Dir4::Down);
+        let mut moving = vec![self.robot];
+        let mut seen = HashSet::from([self.robot]);
+        let mut idx = 0;
+        while idx < moving.len() {
+            let next = moving[idx] + delta;
+            idx += 1;
+            let mut pushed = vec![next];
+            match self.floor.at(next) {
+                Some(Tile::Wall) | None => return,
+                Some(Tile::Empty) | Some(Tile::Robot) => continue,
+                Some(Tile::Box) => {}
+                Some(Tile::BoxLeft) if vertical => pushed.push(next + Dir4::Right.as_vector()),
+                Some(Tile::BoxRight) if vertical => pushed.push(next + Dir4::Left.as_vector()),
+                Some(Tile::BoxLeft) | Some(Tile::BoxRight) => {}
+            }
+            for p in pushed {
+                if seen.insert(p) {
+                    moving.push(p);
+                }
+            }
+        }
+
+        // Lift everything up, then put it all down one step further along.
+        let tiles: Vec<Tile> = moving.iter().map(|&p| *self.floor.at(p).unwrap()).collect();
+        for &p in &moving {
+            *self.floor.at_mut(p).unwrap() = Tile::Empty;
+        }
+        for (&p, tile) in moving.iter().zip(tiles) {
+            *self.floor.at_mut(p + delta).unwrap() = tile;
+        }
+        self.robot += delta;
+    }
+
+    pub fn run(&mut self, moves: &[Dir4]) {
+        for &dir in moves {
+            self.step(dir);
+        }
+    }
+
+    // Sum of 100 * y + x for every box, measured to its left edge.
+    pub fn gps_sum(&self) -> isize {
+        self.floor
+            .points()
+            .filter(|&p| matches!(self.floor.at(p), Some(Tile::Box | Tile::BoxLeft)))
+            .map(|p| 100 * p.y + p.x)
+            .sum()
+    }
+}
+
+impl Display for Warehouse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.floor)
+    }
+}
+
+#[derive(Debug)]
+pub struct Input {
+    warehouse: Warehouse,
+    moves: Vec<Dir4>,
+}
+
+/*
+The warehouse map, a blank line, then the robot's moves as '^', 'v', '<' and
+'>'. The moves may be wrapped over several lines.
+ */
+fn parse_input(input: &str) -> Result<Input> {
+    let (map, move_text) = input.split_once("\n\n").ok_or_else(|| {
+        let line_idx = input.lines().count().saturating_sub(1);
+        let line = input.lines().last().unwrap_or("");
+        let kind = ParseErrorKind::MissingSection("the robot's moves after a blank line");
+        ParseError::end_of_line(Day15::DAY, line_idx, line, kind)
+    })?;
+    let warehouse = Warehouse::parse(map)?;
+
+    let first_move_line = map.lines().count() + 1;
+    let mut moves = Vec::new();
+    for (line_idx, line) in move_text.lines().enumerate() {
+        for (idx, c) in line.char_indices() {
+            let dir = match c {
+                '^' => Dir4::Up,
+                'v' => Dir4::Down,
+                '<' => Dir4::Left,
+                '>' => Dir4::Right,
+                _ => {
+                    let offending = &line[idx..idx + c.len_utf8()];
+                    let line_idx = first_move_line + line_idx;
+                    let kind = ParseErrorKind::UnexpectedChar;
+                    return Err(
+                        ParseError::in_line(Day15::DAY, line_idx, line, offending, kind).into(),
+                    );
+                }
+            };
+            moves.push(dir);
+        }
+    }
+    return Ok(Input { warehouse, moves });
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+
+    type Input = Input;
+    type Answer1 = isize;
+    type Answer2 = isize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<isize> {
+        let mut warehouse = input.warehouse.clone();
+        warehouse.run(&input.moves);
+        return Ok(warehouse.gps_sum());
+    }
+
+    fn part2(input: &Self::Input) -> Result<isize> {
+        let mut warehouse = input.warehouse.widen();
+        warehouse.run(&input.moves);
+        return Ok(warehouse.gps_sum());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_TEXT: &str = "##########
+#..O..O.O#
+#......O.#
+#.OO..O.O#
+#..O@..O.#
+#O#..O...#
+#O..O..O.#
+#.OO.O.OO#
+#....O...#
+##########
+
+<vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+<<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+>^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+<><^^>^^^<><vvvvv^v<v<<>^v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
+
+    const SMALL_TEXT: &str = "########
+#..O.O.#
+##@.O..#
+#...O..#
+#.#.O..#
+#...O..#
+#......#
+########
+
+<^^>>>vv<v>>v<<";
+
+    #[test]
+    fn run_part1_example() {
+        let expected = 10092;
+        let result = Day15::solve_part1(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part1_small_example() {
+        let expected = 2028;
+        let result = Day15::solve_part1(SMALL_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part2_example() {
+        let expected = 9021;
+        let result = Day15::solve_part2(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn small_example_final_state() {
+        let input = Day15::parse(SMALL_TEXT).unwrap();
+        let mut warehouse = input.warehouse.clone();
+        warehouse.run(&input.moves);
+        let expected = "########
+#....OO#
+##.....#
+#.....O#
+#.#O@..#
+#...O..#
+#...O..#
+########";
+        assert_eq!(warehouse.to_string(), expected);
+    }
+
+    #[test]
+    fn wide_boxes_push_as_a_cascade() {
+        let input = Day15::parse(
+            "#######
+#...#.#
+#.....#
+#..OO@#
+#..O..#
+#.....#
+#######
+
+<vv<<^^<<^^",
+        )
+        .unwrap();
+        let mut warehouse = input.warehouse.widen();
+        assert_eq!(
+            warehouse.to_string(),
+            "##############
+##......##..##
+##..........##
+##....[][]@.##
+##....[]....##
+##..........##
+##############"
+        );
+        warehouse.run(&input.moves);
+        assert_eq!(
+            warehouse.to_string(),
+            "##############
+##...[].##..##
+##...@.[]...##
+##....[]....##
+##..........##
+##..........##
+##############"
+        );
+    }
+
+    #[test]
+    fn blocked_wide_box_holds_everything_back() {
+        // The upper box is stuck under a wall, so the lower one can't move either
+        let input = Day15::parse(
+            "##########
+##....#..#
+##...[]..#
+##..[]...#
+##..@....#
+##########
+
+^",
+        )
+        .unwrap();
+        let mut warehouse = input.warehouse.clone();
+        warehouse.run(&input.moves);
+        assert_eq!(warehouse, input.warehouse);
+    }
+
+    #[test]
+    fn missing_moves_are_reported() {
+        let error = Day15::parse("###\n#@#\n###").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 15 input, line 3, column 4: missing the robot's moves after a blank line"
+        );
+    }
+}
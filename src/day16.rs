@@ -0,0 +1,248 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+};
+
+use crate::{
+    error::{Error, ParseError, ParseErrorKind, Result},
+    geometry::{Dir4, Point},
+    grid::Grid,
+    solution::Solution,
+};
+
+const STEP_COST: u64 = 1;
+const TURN_COST: u64 = 1000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tile {
+    Wall,
+    Open,
+    Start,
+    End,
+}
+
+// Where the reindeer is, and which way it's facing.
+type State = (Point, Dir4);
+
+#[derive(Debug)]
+pub struct Maze {
+    tiles: Grid<Tile>,
+    start: Point,
+    end: Point,
+}
+
+impl Maze {
+    fn parse(input: &str) -> Result<Self> {
+        let tiles = Grid::parse(Day16::DAY, input, |c| match c {
+            '#' => Some(Tile::Wall),
+            '.' => Some(Tile::Open),
+            'S' => Some(Tile::Start),
+            'E' => Some(Tile::End),
+            _ => None,
+        })?;
+        let find = |wanted: Tile, section| {
+            tiles
+                .position(|&tile| tile == wanted)
+                .map(Point::from)
+                .ok_or_else(|| {
+                    let kind = ParseErrorKind::MissingSection(section);
+                    ParseError::end_of_line(Day16::DAY, 0, "", kind)
+                })
+        };
+        let start = find(Tile::Start, "the start `S`")?;
+        let end = find(Tile::End, "the end `E`")?;
+        Ok(Self { tiles, start, end })
+    }
+
+    fn is_open(&self, p: Point) -> bool {
+        matches!(self.tiles.at(p), Some(Tile::Open | Tile::Start | Tile::End))
+    }
+
+    // The states reachable in one move, and what each costs.
+    fn moves(&self, (pos, facing): State) -> impl Iterator<Item = (State, u64)> + '_ {
+        let ahead = pos + facing.as_vector();
+        let step = self.is_open(ahead).then_some(((ahead, facing), STEP_COST));
+        let turns = [facing.clockwise(), facing.counter_clockwise()]
+            .map(|turned| ((pos, turned), TURN_COST));
+        step.into_iter().chain(turns)
+    }
+
+    // The states that reach `state` in one move, and what that move costs.
+    fn moves_into(&self, (pos, facing): State) -> impl Iterator<Item = (State, u64)> {
+        let behind = ((pos - facing.as_vector(), facing), STEP_COST);
+        let turns = [facing.clockwise(), facing.counter_clockwise()]
+            .map(|turned| ((pos, turned), TURN_COST));
+        std::iter::once(behind).chain(turns)
+    }
+
+    /*
+    Dijkstra from the start (facing east) to every reachable state. Returns the
+    lowest score of each state.
+     */
+    fn scores(&self) -> HashMap<State, u64> {
+        let start = (self.start, Dir4::Right);
+        let mut best = HashMap::from([(start, 0)]);
+        let mut queue = BinaryHeap::from([Reverse((0, start))]);
+        while let Some(Reverse((score, state))) = queue.pop() {
+            if best.get(&state).is_some_and(|&known| known < score) {
+                // Already found a cheaper way here
+                continue;
+            }
+            for (next, cost) in self.moves(state) {
+                let next_score = score + cost;
+                if best.get(&next).is_none_or(|&known| next_score < known) {
+                    best.insert(next, next_score);
+                    queue.push(Reverse((next_score, next)));
+                }
+            }
+        }
+        return best;
+    }
+
+    fn lowest_score(&self, scores: &HashMap<State, u64>) -> Option<u64> {
+        Dir4::ALL
+            .iter()
+            .filter_map(|&dir| scores.get(&(self.end, dir)))
+            .min()
+            .copied()
+    }
+
+    /*
+    Every tile on at least one of the cheapest routes.
+
+    Walks backwards from the end: a state is on a best route if one of its
+    moves leads to a state on a best route, and its score plus that move's cost
+    is exactly that state's score.
+     */
+    fn best_route_tiles(&self, scores: &HashMap<State, u64>, lowest: u64) -> HashSet<Point> {
+        let mut on_route: HashSet<State> = Dir4::ALL
+            .iter()
+            .map(|&dir| (self.end, dir))
+            .filter(|state| scores.get(state) == Some(&lowest))
+            .collect();
+        let mut stack: Vec<State> = on_route.iter().copied().collect();
+        while let Some(state) = stack.pop() {
+            let score = scores[&state];
+            for (previous, cost) in self.moves_into(state) {
+                let fits = scores.get(&previous).is_some_and(|&s| s + cost == score);
+                if fits && on_route.insert(previous) {
+                    stack.push(previous);
+                }
+            }
+        }
+        return on_route.into_iter().map(|(pos, _)| pos).collect();
+    }
+}
+
+fn no_route(part: u8) -> Error {
+    Error::NoSolution {
+        day: Day16::DAY,
+        part,
+        reason: "there's no way from `S` to `E`".to_string(),
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+
+    type Input = Maze;
+    type Answer1 = u64;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Maze::parse(input)
+    }
+
+    fn part1(maze: &Self::Input) -> Result<u64> {
+        let scores = maze.scores();
+        maze.lowest_score(&scores).ok_or_else(|| no_route(1))
+    }
+
+    fn part2(maze: &Self::Input) -> Result<usize> {
+        let scores = maze.scores();
+        let lowest = maze.lowest_score(&scores).ok_or_else(|| no_route(2))?;
+        return Ok(maze.best_route_tiles(&scores, lowest).len());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_TEXT: &str = "###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############";
+
+    const SECOND_TEXT: &str = "#################
+#...#...#...#..E#
+#.#.#.#.#.#.#.#.#
+#.#.#.#...#...#.#
+#.#.#.#.###.#.#.#
+#...#.#.#.....#.#
+#.#.#.#.#.#####.#
+#.#...#.#.#.....#
+#.#.#####.#.###.#
+#.#.#.......#...#
+#.#.###.#####.###
+#.#.#...#.....#.#
+#.#.#.#####.###.#
+#.#.#.........#.#
+#.#.#.#########.#
+#S#.............#
+#################";
+
+    #[test]
+    fn run_part1_example() {
+        let expected = 7036;
+        let result = Day16::solve_part1(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part1_second_example() {
+        let expected = 11048;
+        let result = Day16::solve_part1(SECOND_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part2_example() {
+        let expected = 45;
+        let result = Day16::solve_part2(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part2_second_example() {
+        let expected = 64;
+        let result = Day16::solve_part2(SECOND_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn walled_in_end_has_no_route() {
+        let error = Day16::solve_part1("#####\n#S#E#\n#####").unwrap_err();
+        assert!(matches!(
+            error,
+            Error::NoSolution {
+                day: 16,
+                part: 1,
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,421 @@
+use std::fmt::{self, Display};
+
+use crate::{
+    error::{parse_number, Error, ParseError, ParseErrorKind, Result},
+    solution::Solution,
+};
+
+// A program that hasn't halted after this many instructions probably never will.
+const MAX_STEPS: usize = 1_000_000;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Opcode {
+    // A = A >> combo
+    Adv,
+    // B = B ^ literal
+    Bxl,
+    // B = combo % 8
+    Bst,
+    // jump to literal if A != 0
+    Jnz,
+    // B = B ^ C (the operand is ignored)
+    Bxc,
+    // output combo % 8
+    Out,
+    // B = A >> combo
+    Bdv,
+    // C = A >> combo
+    Cdv,
+}
+
+impl Opcode {
+    const ALL: [Opcode; 8] = [
+        Opcode::Adv,
+        Opcode::Bxl,
+        Opcode::Bst,
+        Opcode::Jnz,
+        Opcode::Bxc,
+        Opcode::Out,
+        Opcode::Bdv,
+        Opcode::Cdv,
+    ];
+
+    fn takes_combo(self) -> bool {
+        matches!(
+            self,
+            Opcode::Adv | Opcode::Bst | Opcode::Out | Opcode::Bdv | Opcode::Cdv
+        )
+    }
+
+    fn mnemonic(self) -> &'static str {
+        match self {
+            Opcode::Adv => "adv",
+            Opcode::Bxl => "bxl",
+            Opcode::Bst => "bst",
+            Opcode::Jnz => "jnz",
+            Opcode::Bxc => "bxc",
+            Opcode::Out => "out",
+            Opcode::Bdv => "bdv",
+            Opcode::Cdv => "cdv",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Registers {
+    a: u64,
+    b: u64,
+    c: u64,
+}
+
+impl Registers {
+    /*
+    Combo operands 0-3 are the literal values, and 4-6 read registers A, B and
+    C. 7 is reserved, and parsing rejects programs that use it. It also rejects
+    jumps to odd addresses, which would read the program out of step and could
+    land on a 7 that was only ever meant as an opcode.
+     */
+    fn combo(&self, operand: u8) -> u64 {
+        match operand {
+            0..=3 => operand as u64,
+            4 => self.a,
+            5 => self.b,
+            6 => self.c,
+            _ => unreachable!("combo operand 7 is rejected by the parser"),
+        }
+    }
+
+    /*
+    The division shared by adv, bdv and cdv: A over 2 to the power of the combo
+    operand, rounded down. A shift of 64 or more leaves nothing of A.
+     */
+    fn divide_a(&self, operand: u8) -> u64 {
+        u32::try_from(self.combo(operand))
+            .ok()
+            .and_then(|shift| self.a.checked_shr(shift))
+            .unwrap_or(0)
+    }
+}
+
+/*
+The program as a list of 3-bit numbers, opcodes and operands taking turns.
+
+Prints as a disassembly listing: one instruction per line with its address,
+and combo operands that read a register shown by the register's name.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Program {
+    code: Vec<u8>,
+}
+
+impl Program {
+    /*
+    Run until the instruction pointer walks off the end, and return the outputs.
+    Gives up with an error if that takes more than `MAX_STEPS` instructions.
+     */
+    fn run(&self, mut registers: Registers) -> Result<Vec<u8>> {
+        let mut output = Vec::new();
+        let mut ip = 0;
+        let mut steps = 0;
+        while ip + 1 < self.code.len() {
+            steps += 1;
+            if steps > MAX_STEPS {
+                return Err(Error::NoSolution {
+                    day: Day17::DAY,
+                    part: 1,
+                    reason: format!("the program was still running after {MAX_STEPS} steps"),
+                });
+            }
+            let op = Opcode::ALL[self.code[ip] as usize];
+            let operand = self.code[ip + 1];
+            let literal = operand as u64;
+            ip += 2;
+            match op {
+                Opcode::Adv => registers.a = registers.divide_a(operand),
+                Opcode::Bxl => registers.b ^= literal,
+                Opcode::Bst => registers.b = registers.combo(operand) % 8,
+                Opcode::Jnz => {
+                    if registers.a != 0 {
+                        ip = operand as usize;
+                    }
+                }
+                Opcode::Bxc => registers.b ^= registers.c,
+                Opcode::Out => output.push((registers.combo(operand) % 8) as u8),
+                Opcode::Bdv => registers.b = registers.divide_a(operand),
+                Opcode::Cdv => registers.c = registers.divide_a(operand),
+            }
+        }
+        return Ok(output);
+    }
+
+    /*
+    Find the lowest value of register A that makes the program print itself.
+
+    This relies on the shape every puzzle input has: a single loop that prints
+    one number, shifts A right by 3 bits, and jumps back to the start while A
+    isn't 0. Each output then only depends on the 3-bit chunks of A from that
+    point up, so A can be built 3 bits at a time starting from the top. The last
+    output comes from the top chunk alone, the one before from the top two
+    chunks, and so on. Every chunk that reproduces the tail of the program is
+    kept as a candidate, since a dead end may only show up further down.
+     */
+    fn find_quine(&self, registers: Registers) -> Result<Option<u64>> {
+        let mut candidates = vec![0u64];
+        for start in (0..self.code.len()).rev() {
+            let mut next = Vec::new();
+            for prefix in &candidates {
+                for chunk in 0..8 {
+                    let a = prefix << 3 | chunk;
+                    let output = self.run(Registers { a, ..registers })?;
+                    if output == self.code[start..] {
+                        next.push(a);
+                    }
+                }
+            }
+            candidates = next;
+        }
+        return Ok(candidates.into_iter().min());
+    }
+}
+
+impl Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (address, pair) in self.code.chunks(2).enumerate() {
+            if address > 0 {
+                writeln!(f)?;
+            }
+            let op = Opcode::ALL[pair[0] as usize];
+            write!(f, "{:2}: {}", address * 2, op.mnemonic())?;
+            match pair.get(1) {
+                Some(&operand) if op.takes_combo() => {
+                    let names = ["0", "1", "2", "3", "A", "B", "C"];
+                    write!(f, " {}", names[operand as usize])?;
+                }
+                Some(&operand) if op != Opcode::Bxc => write!(f, " {operand}")?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct Computer {
+    registers: Registers,
+    program: Program,
+}
+
+/*
+The three registers, a blank line, then the program:
+
+    Register A: 729
+    Register B: 0
+    Register C: 0
+
+    Program: 0,1,5,4,3,0
+ */
+fn parse_computer(input: &str) -> Result<Computer> {
+    let mut lines = input.lines().enumerate();
+    let mut register = |name: &'static str| -> Result<u64> {
+        let (line_idx, line) = lines.next().unwrap_or((0, ""));
+        let value = line.strip_prefix(name).ok_or_else(|| {
+            let kind = ParseErrorKind::MissingField(name);
+            ParseError::in_line(Day17::DAY, line_idx, line, line, kind)
+        })?;
+        Ok(parse_number(Day17::DAY, line_idx, line, value.trim())?)
+    };
+    let registers = Registers {
+        a: register("Register A:")?,
+        b: register("Register B:")?,
+        c: register("Register C:")?,
+    };
+
+    let (line_idx, line) = lines.find(|(_, line)| !line.is_empty()).unwrap_or((0, ""));
+    let missing = || {
+        let kind = ParseErrorKind::MissingSection("the `Program:`");
+        ParseError::end_of_line(Day17::DAY, line_idx, line, kind)
+    };
+    let numbers = line.strip_prefix("Program:").ok_or_else(missing)?;
+    let mut code = Vec::new();
+    for token in numbers.trim().split(',') {
+        let value: u8 = parse_number(Day17::DAY, line_idx, line, token)?;
+        // Every number is 3 bits, no combo operand can be 7, and jumps stay in step
+        let is_operand = code.len() % 2 == 1;
+        let previous = code.last().map(|&op: &u8| Opcode::ALL[op as usize]);
+        let bad_combo = is_operand && value == 7 && previous.is_some_and(Opcode::takes_combo);
+        let odd_jump = is_operand && value % 2 == 1 && previous == Some(Opcode::Jnz);
+        if value > 7 || bad_combo || odd_jump {
+            let kind = ParseErrorKind::UnexpectedChar;
+            return Err(ParseError::in_line(Day17::DAY, line_idx, line, token, kind).into());
+        }
+        code.push(value);
+    }
+    let program = Program { code };
+    return Ok(Computer { registers, program });
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+
+    type Input = Computer;
+    type Answer1 = String;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_computer(input)
+    }
+
+    fn part1(computer: &Self::Input) -> Result<String> {
+        let output = computer.program.run(computer.registers)?;
+        let text: Vec<String> = output.iter().map(|value| value.to_string()).collect();
+        return Ok(text.join(","));
+    }
+
+    fn part2(computer: &Self::Input) -> Result<u64> {
+        let no_quine = |reason: String| Error::NoSolution {
+            day: Day17::DAY,
+            part: 2,
+            reason,
+        };
+        let found = computer
+            .program
+            .find_quine(computer.registers)
+            .map_err(|e| match e {
+                Error::NoSolution { reason, .. } => no_quine(reason),
+                e => e,
+            })?;
+        found.ok_or_else(|| {
+            no_quine(format!(
+                "no value of register A makes this program print itself:\n{}",
+                computer.program
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_TEXT: &str = "Register A: 729
+Register B: 0
+Register C: 0
+
+Program: 0,1,5,4,3,0";
+
+    const QUINE_TEXT: &str = "Register A: 2024
+Register B: 0
+Register C: 0
+
+Program: 0,3,5,4,3,0";
+
+    fn program(code: &[u8]) -> Program {
+        Program {
+            code: code.to_vec(),
+        }
+    }
+
+    #[test]
+    fn run_part1_example() {
+        let expected = "4,6,3,5,6,3,5,2,1,0";
+        let result = Day17::solve_part1(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part2_example() {
+        let expected = 117440;
+        let result = Day17::solve_part2(QUINE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn small_programs() {
+        // The examples from the puzzle text that check a single register
+        let mut registers = Registers {
+            c: 9,
+            ..Default::default()
+        };
+        assert_eq!(program(&[2, 6]).run(registers).unwrap(), vec![]);
+
+        registers = Registers {
+            a: 10,
+            ..Default::default()
+        };
+        let output = program(&[5, 0, 5, 1, 5, 4]).run(registers).unwrap();
+        assert_eq!(output, vec![0, 1, 2]);
+
+        registers = Registers {
+            a: 2024,
+            ..Default::default()
+        };
+        let output = program(&[0, 1, 5, 4, 3, 0]).run(registers).unwrap();
+        assert_eq!(output, vec![4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0]);
+    }
+
+    #[test]
+    fn oversized_shifts_divide_to_zero() {
+        // adv A with A = 100, then print it
+        let registers = Registers {
+            a: 100,
+            ..Default::default()
+        };
+        assert_eq!(program(&[0, 4, 5, 4]).run(registers).unwrap(), vec![0]);
+        // bdv and cdv by a shift far past the register's width
+        let registers = Registers {
+            a: 7,
+            b: u64::MAX,
+            c: 64,
+        };
+        let output = program(&[6, 5, 5, 5, 7, 6, 5, 6]).run(registers).unwrap();
+        assert_eq!(output, vec![0, 0]);
+        let input = "Register A: 100\nRegister B: 0\nRegister C: 0\n\nProgram: 0,4,5,4";
+        assert_eq!(Day17::solve_part1(input).unwrap(), "0");
+    }
+
+    #[test]
+    fn disassemble() {
+        let listing = program(&[2, 4, 1, 5, 7, 5, 4, 3, 5, 5, 0, 3, 3, 0]).to_string();
+        let expected = " 0: bst A
+ 2: bxl 5
+ 4: cdv B
+ 6: bxc
+ 8: out B
+10: adv 3
+12: jnz 0";
+        assert_eq!(listing, expected);
+    }
+
+    #[test]
+    fn endless_loop_gives_up() {
+        let registers = Registers {
+            a: 1,
+            ..Default::default()
+        };
+        assert!(program(&[3, 0]).run(registers).is_err());
+    }
+
+    #[test]
+    fn reserved_combo_operand_is_rejected() {
+        let error = Day17::parse("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 1,7,5,7")
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 17 input, line 5, column 16: unexpected character `7`"
+        );
+    }
+
+    #[test]
+    fn odd_jump_target_is_rejected() {
+        // Jumping to 3 would read the `5, 7` straddling two instructions as `out 7`
+        let error =
+            Day17::parse("Register A: 1\nRegister B: 0\nRegister C: 0\n\nProgram: 3,3,0,5,7,0")
+                .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 17 input, line 5, column 12: unexpected character `3`"
+        );
+    }
+}
@@ -0,0 +1,231 @@
+use std::collections::VecDeque;
+
+use crate::{
+    error::{parse_number, Error, ParseError, ParseErrorKind, Result},
+    geometry::Point,
+    grid::Grid,
+    solution::Solution,
+};
+
+/*
+The memory space being corrupted: a square `size` cells wide, from (0, 0) to
+the exit at (size - 1, size - 1). Part 1 looks at it after `fallen` bytes.
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MemorySpace {
+    pub size: isize,
+    pub fallen: usize,
+}
+
+// The real puzzle's memory space
+pub const REAL_SPACE: MemorySpace = MemorySpace {
+    size: 71,
+    fallen: 1024,
+};
+
+/*
+When each cell gets corrupted: `Some(n)` for the nth byte to fall (from 0),
+`None` if it stays safe. "The first n bytes have fallen" then means every cell
+with a time below n, so one grid serves every point in time.
+ */
+fn corruption_times(bytes: &[Point], space: MemorySpace, part: u8) -> Result<Grid<Option<usize>>> {
+    let mut times = Grid::filled(space.size, space.size, None);
+    for (time, &byte) in bytes.iter().enumerate() {
+        let cell = times.at_mut(byte).ok_or_else(|| Error::NoSolution {
+            day: Day18::DAY,
+            part,
+            reason: format!("byte {byte:?} falls outside the memory space"),
+        })?;
+        // Only the first byte to land on a cell matters
+        cell.get_or_insert(time);
+    }
+    return Ok(times);
+}
+
+// Fewest steps from the top-left to the exit once `fallen` bytes are down.
+fn shortest_path(times: &Grid<Option<usize>>, fallen: usize) -> Option<usize> {
+    let start = Point::new(0, 0);
+    let exit = Point::new(times.width() - 1, times.height() - 1);
+    let is_safe = |p: Point| times.at(p).is_some_and(|t| t.is_none_or(|t| t >= fallen));
+    if !is_safe(start) {
+        return None;
+    }
+
+    let mut steps = times.map(|_| None);
+    *steps.at_mut(start).unwrap() = Some(0);
+    let mut queue = VecDeque::from([start]);
+    while let Some(p) = queue.pop_front() {
+        let so_far = steps.at(p).unwrap().unwrap();
+        if p == exit {
+            return Some(so_far);
+        }
+        for next in times.neighbours4(p) {
+            if is_safe(next) && steps.at(next) == Some(&None) {
+                *steps.at_mut(next).unwrap() = Some(so_far + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+    return None;
+}
+
+/*
+The first byte that cuts the exit off.
+
+Once the exit is cut off it stays that way, since bytes only ever pile up. So
+binary search for the smallest number of fallen bytes with no path: that takes
+about log2(bytes) searches instead of one per byte.
+ */
+fn first_blocking_byte(bytes: &[Point], times: &Grid<Option<usize>>) -> Option<Point> {
+    let (mut open, mut blocked) = (0, bytes.len());
+    if shortest_path(times, blocked).is_some() {
+        return None;
+    }
+    // Invariant: there's a path after `open` bytes, and none after `blocked` bytes
+    while blocked - open > 1 {
+        let middle = (open + blocked) / 2;
+        if shortest_path(times, middle).is_some() {
+            open = middle;
+        } else {
+            blocked = middle;
+        }
+    }
+    return Some(bytes[blocked - 1]);
+}
+
+fn parse_bytes(input: &str) -> Result<Vec<Point>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(line_idx, line)| -> Result<Point> {
+            let (x, y) = line.split_once(',').ok_or_else(|| {
+                ParseError::end_of_line(
+                    Day18::DAY,
+                    line_idx,
+                    line,
+                    ParseErrorKind::MissingField("y coordinate"),
+                )
+            })?;
+            let x = parse_number(Day18::DAY, line_idx, line, x)?;
+            let y = parse_number(Day18::DAY, line_idx, line, y)?;
+            return Ok(Point::new(x, y));
+        })
+        .collect()
+}
+
+fn min_steps(bytes: &[Point], space: MemorySpace) -> Result<usize> {
+    let times = corruption_times(bytes, space, 1)?;
+    shortest_path(&times, space.fallen).ok_or_else(|| Error::NoSolution {
+        day: Day18::DAY,
+        part: 1,
+        reason: format!("the exit is already cut off after {} bytes", space.fallen),
+    })
+}
+
+fn blocking_byte(bytes: &[Point], space: MemorySpace) -> Result<String> {
+    let times = corruption_times(bytes, space, 2)?;
+    let byte = first_blocking_byte(bytes, &times).ok_or_else(|| Error::NoSolution {
+        day: Day18::DAY,
+        part: 2,
+        reason: "the exit is still reachable after every byte has fallen".to_string(),
+    })?;
+    return Ok(format!("{},{}", byte.x, byte.y));
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    const DAY: u8 = 18;
+
+    type Input = Vec<Point>;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_bytes(input)
+    }
+
+    fn part1(bytes: &Self::Input) -> Result<usize> {
+        min_steps(bytes, REAL_SPACE)
+    }
+
+    fn part2(bytes: &Self::Input) -> Result<String> {
+        blocking_byte(bytes, REAL_SPACE)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // The memory space used by the puzzle's example
+    const EXAMPLE_SPACE: MemorySpace = MemorySpace {
+        size: 7,
+        fallen: 12,
+    };
+
+    const SAMPLE_TEXT: &str = "5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0";
+
+    #[test]
+    fn run_part1_example() {
+        let expected = 22;
+        let bytes = Day18::parse(SAMPLE_TEXT).unwrap();
+        let result = min_steps(&bytes, EXAMPLE_SPACE).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part2_example() {
+        let expected = "6,1";
+        let bytes = Day18::parse(SAMPLE_TEXT).unwrap();
+        let result = blocking_byte(&bytes, EXAMPLE_SPACE).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn binary_search_matches_byte_by_byte() {
+        let bytes = Day18::parse(SAMPLE_TEXT).unwrap();
+        let times = corruption_times(&bytes, EXAMPLE_SPACE, 2).unwrap();
+        let slow = (1..=bytes.len())
+            .find(|&n| shortest_path(&times, n).is_none())
+            .map(|n| bytes[n - 1]);
+        assert_eq!(first_blocking_byte(&bytes, &times), slow);
+    }
+
+    #[test]
+    fn open_space_never_blocks() {
+        let bytes = Day18::parse("1,1\n3,3").unwrap();
+        assert_eq!(min_steps(&bytes, EXAMPLE_SPACE).unwrap(), 12);
+        assert!(blocking_byte(&bytes, EXAMPLE_SPACE).is_err());
+    }
+
+    #[test]
+    fn byte_outside_space_is_an_error() {
+        let bytes = Day18::parse("7,0").unwrap();
+        assert!(min_steps(&bytes, EXAMPLE_SPACE).is_err());
+    }
+}
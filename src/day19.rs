@@ -0,0 +1,224 @@
+use crate::{
+    error::{ParseError, ParseErrorKind, Result},
+    solution::Solution,
+};
+
+// The stripe colours: white, blue, black, red and green.
+const COLOURS: [char; 5] = ['w', 'u', 'b', 'r', 'g'];
+
+fn colour_index(c: char) -> Option<usize> {
+    COLOURS.iter().position(|&colour| colour == c)
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    children: [Option<usize>; COLOURS.len()],
+    // A towel pattern ends here
+    is_pattern: bool,
+}
+
+/*
+A trie of the towel patterns, one level per stripe.
+
+Walking down it alongside a design finds every towel that fits at the start of
+the design in one pass, however many towels there are.
+ */
+#[derive(Debug)]
+pub struct TowelTrie {
+    nodes: Vec<Node>,
+}
+
+impl TowelTrie {
+    fn new() -> Self {
+        Self {
+            nodes: vec![Node::default()],
+        }
+    }
+
+    fn insert(&mut self, stripes: &[usize]) {
+        let mut node = 0;
+        for &stripe in stripes {
+            node = match self.nodes[node].children[stripe] {
+                Some(child) => child,
+                None => {
+                    self.nodes.push(Node::default());
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].children[stripe] = Some(child);
+                    child
+                }
+            };
+        }
+        self.nodes[node].is_pattern = true;
+    }
+
+    // Lengths of every towel pattern that `design` starts with.
+    fn prefixes<'a>(&'a self, design: &'a [usize]) -> impl Iterator<Item = usize> + 'a {
+        let mut node = Some(0);
+        design
+            .iter()
+            .enumerate()
+            .map_while(move |(idx, &stripe)| {
+                node = self.nodes[node?].children[stripe];
+                let matched = self.nodes[node?].is_pattern.then_some(idx + 1);
+                Some(matched)
+            })
+            .flatten()
+    }
+
+    /*
+    How many ways there are to line towels up to make `design`.
+
+    `ways[i]` counts the arrangements for the tail of the design from stripe
+    `i`. A towel of length `n` fitting at `i` adds all of `ways[i + n]`, and the
+    empty tail has exactly one arrangement. Filling it in from the end means
+    every tail is only worked out once.
+     */
+    fn arrangements(&self, design: &[usize]) -> u64 {
+        let mut ways = vec![0u64; design.len() + 1];
+        ways[design.len()] = 1;
+        for start in (0..design.len()).rev() {
+            ways[start] = self
+                .prefixes(&design[start..])
+                .map(|len| ways[start + len])
+                .sum();
+        }
+        return ways[0];
+    }
+}
+
+#[derive(Debug)]
+pub struct Onsen {
+    towels: TowelTrie,
+    // Each design, as colour indices
+    designs: Vec<Vec<usize>>,
+}
+
+fn parse_stripes(
+    line_idx: usize,
+    line: &str,
+    text: &str,
+) -> std::result::Result<Vec<usize>, ParseError> {
+    text.char_indices()
+        .map(|(idx, c)| {
+            colour_index(c).ok_or_else(|| {
+                let offending = &text[idx..idx + c.len_utf8()];
+                let kind = ParseErrorKind::UnexpectedChar;
+                ParseError::in_line(Day19::DAY, line_idx, line, offending, kind)
+            })
+        })
+        .collect()
+}
+
+/*
+The towel patterns on the first line, separated by ", ". Then a blank line,
+then one design per line.
+ */
+fn parse_onsen(input: &str) -> Result<Onsen> {
+    let mut lines = input.lines().enumerate();
+    let (_, towel_line) = lines.next().unwrap_or((0, ""));
+    let mut towels = TowelTrie::new();
+    for pattern in towel_line.split(", ") {
+        towels.insert(&parse_stripes(0, towel_line, pattern)?);
+    }
+
+    if lines.next().is_none_or(|(_, line)| !line.is_empty()) {
+        let kind = ParseErrorKind::MissingSection("the designs after a blank line");
+        return Err(ParseError::end_of_line(Day19::DAY, 0, towel_line, kind).into());
+    }
+    let designs = lines
+        .map(|(line_idx, line)| parse_stripes(line_idx, line, line))
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    return Ok(Onsen { towels, designs });
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    const DAY: u8 = 19;
+
+    type Input = Onsen;
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_onsen(input)
+    }
+
+    // How many designs can be made at all
+    fn part1(onsen: &Self::Input) -> Result<usize> {
+        let possible = onsen
+            .designs
+            .iter()
+            .filter(|design| onsen.towels.arrangements(design) > 0)
+            .count();
+        return Ok(possible);
+    }
+
+    // How many ways there are to make each design, added up
+    fn part2(onsen: &Self::Input) -> Result<u64> {
+        let total = onsen
+            .designs
+            .iter()
+            .map(|design| onsen.towels.arrangements(design))
+            .sum();
+        return Ok(total);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_TEXT: &str = "r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb";
+
+    #[test]
+    fn run_part1_example() {
+        let expected = 6;
+        let result = Day19::solve_part1(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part2_example() {
+        let expected = 16;
+        let result = Day19::solve_part2(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn arrangements_per_design() {
+        let onsen = Day19::parse(SAMPLE_TEXT).unwrap();
+        let counts: Vec<u64> = onsen
+            .designs
+            .iter()
+            .map(|design| onsen.towels.arrangements(design))
+            .collect();
+        assert_eq!(counts, vec![2, 1, 4, 6, 0, 1, 2, 0]);
+    }
+
+    #[test]
+    fn trie_finds_every_prefix() {
+        let onsen = Day19::parse("b, bw, bwu, u\n\nbwu").unwrap();
+        let design = &onsen.designs[0];
+        let lengths: Vec<usize> = onsen.towels.prefixes(design).collect();
+        assert_eq!(lengths, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn unknown_colour_is_reported() {
+        let error = Day19::parse("r, wr\n\nbrxr").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 19 input, line 3, column 3: unexpected character `x`"
+        );
+    }
+}
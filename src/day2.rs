@@ -1,12 +1,17 @@
 use itertools::Itertools;
 
+use crate::{
+    error::{parse_number, Result},
+    solution::Solution,
+};
+
 type Record = Vec<i32>;
 
-fn parse_reports(input: &str) -> impl Iterator<Item = Record> + use<'_> {
-    input.split("\n").map(|line| -> Record {
+fn parse_reports(input: &str) -> impl Iterator<Item = Result<Record>> + use<'_> {
+    input.lines().enumerate().map(|(line_idx, line)| -> Result<Record> {
         let parts = line.split(" ");
-        let num_iter = parts.map(|number| number.parse::<i32>().expect("Failed to parse a number"));
-        return num_iter.collect::<Record>();
+        let num_iter = parts.map(|number| parse_number(Day2::DAY, line_idx, line, number));
+        return Ok(num_iter.collect::<std::result::Result<Record, _>>()?);
     })
 }
 
@@ -61,71 +66,90 @@ fn grade_record(record: Record) -> SafetyGrade {
     return SafetyGrade::Safe;
 }
 
-pub fn process_d2p1(input: &str) -> i32 {
-    parse_reports(input)
-        .map(grade_record)
-        .fold(0, |count, grade| {
-            if grade == SafetyGrade::Safe {
-                count + 1
-            } else {
-                count
-            }
-        })
-}
+pub struct Day2;
 
-pub fn process_d2p2(input: &str) -> i32 {
-    // Collect the failed items for a second round of processing
-    let mut needs_another: Vec<(Record, SafetyGrade)> = Vec::new();
-
-    // Same routine as Part 1: Parse text, grade records, count safe ones
-    let passes = parse_reports(input)
-        .map(|record| (record.clone(), grade_record(record)))
-        .fold(0, |count, (record, grade)| {
-            if grade == SafetyGrade::Safe {
-                count + 1
-            } else {
-                needs_another.push((record, grade));
-                count
-            }
-        });
+impl Solution for Day2 {
+    const DAY: u8 = 2;
 
-    // Remove the failing index from each record before reprocessing
-    let needs_another = needs_another
-        .into_iter()
-        .map(|(mut record, grade)| {
-            let idx = match grade {
-                SafetyGrade::Safe => unreachable!(),
-                SafetyGrade::TooSlow(idx) => idx,
-                SafetyGrade::TooFast(idx) => idx,
-                SafetyGrade::Maxima(idx) => idx,
-                SafetyGrade::Minima(idx) => idx,
-            };
-            record.remove(idx);
-            record
-        })
-        .collect::<Vec<Record>>();
-
-    let recovered_passes = needs_another
-        .into_iter()
-        .map(|record| (record.clone(), grade_record(record)))
-        .map(|(record, grade)| {
-            // println!("Record {:?} was graded {:?}", record, grade);
-            grade
-        })
-        .fold(0, |count, grade| {
-            if grade == SafetyGrade::Safe {
-                count + 1
-            } else {
-                count
-            }
-        });
-    // println!(
-    //     "Passes: {}\nRecovered: {}\n  Total: {}",
-    //     passes,
-    //     recovered_passes,
-    //     (passes + recovered_passes)
-    // );
-    passes + recovered_passes
+    type Input = Vec<Record>;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_reports(input).collect()
+    }
+
+    fn part1(reports: &Self::Input) -> Result<i32> {
+        let count = reports
+            .iter()
+            .cloned()
+            .map(grade_record)
+            .fold(0, |count, grade| {
+                if grade == SafetyGrade::Safe {
+                    count + 1
+                } else {
+                    count
+                }
+            });
+        Ok(count)
+    }
+
+    fn part2(reports: &Self::Input) -> Result<i32> {
+        // Collect the failed items for a second round of processing
+        let mut needs_another: Vec<(Record, SafetyGrade)> = Vec::new();
+
+        // Same routine as Part 1: Parse text, grade records, count safe ones
+        let passes = reports
+            .iter()
+            .cloned()
+            .map(|record| (record.clone(), grade_record(record)))
+            .fold(0, |count, (record, grade)| {
+                if grade == SafetyGrade::Safe {
+                    count + 1
+                } else {
+                    needs_another.push((record, grade));
+                    count
+                }
+            });
+
+        // Remove the failing index from each record before reprocessing
+        let needs_another = needs_another
+            .into_iter()
+            .map(|(mut record, grade)| {
+                let idx = match grade {
+                    SafetyGrade::Safe => unreachable!(),
+                    SafetyGrade::TooSlow(idx) => idx,
+                    SafetyGrade::TooFast(idx) => idx,
+                    SafetyGrade::Maxima(idx) => idx,
+                    SafetyGrade::Minima(idx) => idx,
+                };
+                record.remove(idx);
+                record
+            })
+            .collect::<Vec<Record>>();
+
+        let recovered_passes = needs_another
+            .into_iter()
+            .map(|record| (record.clone(), grade_record(record)))
+            .map(|(record, grade)| {
+                // println!("Record {:?} was graded {:?}", record, grade);
+                grade
+            })
+            .fold(0, |count, grade| {
+                if grade == SafetyGrade::Safe {
+                    count + 1
+                } else {
+                    count
+                }
+            });
+        // println!(
+        //     "Passes: {}\nRecovered: {}\n  Total: {}",
+        //     passes,
+        //     recovered_passes,
+        //     (passes + recovered_passes)
+        // );
+        Ok(passes + recovered_passes)
+    }
 }
 
 #[cfg(test)]
@@ -144,14 +168,14 @@ mod day2_tests {
 
     #[test]
     fn run_part1_example() {
-        let result = process_d2p1(SAMPLE_TEXT);
+        let result = Day2::solve_part1(SAMPLE_TEXT).unwrap();
         eprintln!("Got result: {result}");
         assert_eq!(result, 2);
     }
 
     #[test]
     fn run_part2_example() {
-        let result = process_d2p2(SAMPLE_TEXT);
+        let result = Day2::solve_part2(SAMPLE_TEXT).unwrap();
         eprintln!("Got result: {result}");
         assert_eq!(result, 4);
     }
@@ -166,8 +190,17 @@ mod day2_tests {
         program finds `5 9`, rejects it as ::TooFast(0) and deletes the 5.
         The second pass then rejects again with a ::Minima(0)
         */
-        let result = process_d2p2(MORE_EDGE_CASES);
+        let result = Day2::solve_part2(MORE_EDGE_CASES).unwrap();
         eprintln!("Got result: {result}");
         assert_eq!(result, 2);
     }
+
+    #[test]
+    fn parse_bad_level() {
+        let error = Day2::parse("7 6 4 2 1\n1 2 7 8 9\n9 7 6 2x 1").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 2 input, line 3, column 7: expected a number, found `2x`"
+        );
+    }
 }
@@ -0,0 +1,291 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::{self, Display},
+};
+
+use crate::{
+    error::{Error, ParseError, ParseErrorKind, Result},
+    geometry::{Point, Vector},
+    grid::Grid,
+    solution::Solution,
+};
+
+/*
+How far a cheat may go through walls, and how many picoseconds it has to save
+to be worth counting.
+ */
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CheatRules {
+    pub radius: usize,
+    pub min_saving: usize,
+}
+
+pub const PART1_RULES: CheatRules = CheatRules {
+    radius: 2,
+    min_saving: 100,
+};
+
+pub const PART2_RULES: CheatRules = CheatRules {
+    radius: 20,
+    min_saving: 100,
+};
+
+#[derive(Debug)]
+pub struct Racetrack {
+    // `true` for track, `false` for wall
+    track: Grid<bool>,
+    start: Point,
+    end: Point,
+}
+
+impl Racetrack {
+    fn parse(input: &str) -> Result<Self> {
+        let mut start = None;
+        let mut end = None;
+        let chars = Grid::parse(Day20::DAY, input, |c| "#.SE".contains(c).then_some(c))?;
+        for p in chars.points() {
+            match chars.at(p) {
+                Some('S') => start = Some(p),
+                Some('E') => end = Some(p),
+                _ => {}
+            }
+        }
+        let missing = |section| {
+            let kind = ParseErrorKind::MissingSection(section);
+            ParseError::end_of_line(Day20::DAY, 0, "", kind)
+        };
+        Ok(Self {
+            track: chars.map(|&c| c != '#'),
+            start: start.ok_or_else(|| missing("the start `S`"))?,
+            end: end.ok_or_else(|| missing("the end `E`"))?,
+        })
+    }
+
+    // Steps from `from` to every track cell (without cheating), `None` if unreachable.
+    fn distances(&self, from: Point) -> Grid<Option<usize>> {
+        let mut distance = self.track.map(|_| None);
+        *distance.at_mut(from).unwrap() = Some(0);
+        let mut queue = VecDeque::from([from]);
+        while let Some(p) = queue.pop_front() {
+            let so_far = distance.at(p).unwrap().unwrap();
+            for next in self.track.neighbours4(p) {
+                if self.track.at(next) == Some(&true) && distance.at(next) == Some(&None) {
+                    *distance.at_mut(next).unwrap() = Some(so_far + 1);
+                    queue.push_back(next);
+                }
+            }
+        }
+        return distance;
+    }
+}
+
+/*
+The cheats allowed by one set of rules: how many there are, and how many
+save each number of picoseconds.
+
+Prints as the count. The alternate form (`{:#}`, which `run --verbose` uses)
+adds the histogram underneath, one line per saving.
+ */
+#[derive(Debug, PartialEq)]
+pub struct CheatReport {
+    pub count: usize,
+    // picoseconds saved -> number of cheats
+    pub histogram: BTreeMap<usize, usize>,
+}
+
+impl Display for CheatReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.count)?;
+        if f.alternate() {
+            for (saving, cheats) in &self.histogram {
+                write!(f, "\n{saving} picoseconds saved: {cheats}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/*
+Every cheat allowed by `rules`, counted and sorted by the time it saves.
+
+A cheat goes from track cell `a` to track cell `b` within `radius` steps,
+ignoring walls. The race then takes:
+
+    (start to a) + (Manhattan distance a to b) + (b to end)
+
+and whatever that beats the honest time by is the saving.
+ */
+pub fn cheat_report(racetrack: &Racetrack, rules: CheatRules, part: u8) -> Result<CheatReport> {
+    let from_start = racetrack.distances(racetrack.start);
+    let to_end = racetrack.distances(racetrack.end);
+    let honest = from_start
+        .at(racetrack.end)
+        .copied()
+        .flatten()
+        .ok_or_else(|| Error::NoSolution {
+            day: Day20::DAY,
+            part,
+            reason: "the end can't be reached from the start".to_string(),
+        })?;
+
+    let radius = rules.radius as isize;
+    let offsets: Vec<Vector> = (-radius..=radius)
+        .flat_map(|dx| (-radius..=radius).map(move |dy| Vector::new(dx, dy)))
+        .filter(|v| v.manhattan_length() <= rules.radius)
+        .collect();
+
+    let mut histogram = BTreeMap::new();
+    for a in from_start.points() {
+        let Some(&Some(to_a)) = from_start.at(a) else {
+            continue;
+        };
+        for &offset in &offsets {
+            let b = a + offset;
+            let Some(&Some(from_b)) = to_end.at(b) else {
+                continue;
+            };
+            let cheating = to_a + offset.manhattan_length() + from_b;
+            let saving = honest.saturating_sub(cheating);
+            if saving > 0 && saving >= rules.min_saving {
+                *histogram.entry(saving).or_insert(0) += 1;
+            }
+        }
+    }
+    let count = histogram.values().sum();
+    return Ok(CheatReport { count, histogram });
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    const DAY: u8 = 20;
+
+    type Input = Racetrack;
+    type Answer1 = CheatReport;
+    type Answer2 = CheatReport;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Racetrack::parse(input)
+    }
+
+    fn part1(racetrack: &Self::Input) -> Result<CheatReport> {
+        cheat_report(racetrack, PART1_RULES, 1)
+    }
+
+    fn part2(racetrack: &Self::Input) -> Result<CheatReport> {
+        cheat_report(racetrack, PART2_RULES, 2)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_TEXT: &str = "###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############";
+
+    #[test]
+    fn run_part1_example() {
+        // The example's cheats are all small, so count every one that saves time
+        let expected = 44;
+        let racetrack = Day20::parse(SAMPLE_TEXT).unwrap();
+        let rules = CheatRules {
+            min_saving: 1,
+            ..PART1_RULES
+        };
+        let result = cheat_report(&racetrack, rules, 1).unwrap().count;
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part2_example() {
+        let expected = 285;
+        let racetrack = Day20::parse(SAMPLE_TEXT).unwrap();
+        let rules = CheatRules {
+            min_saving: 50,
+            ..PART2_RULES
+        };
+        let result = cheat_report(&racetrack, rules, 2).unwrap().count;
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn part1_histogram() {
+        let racetrack = Day20::parse(SAMPLE_TEXT).unwrap();
+        let rules = CheatRules {
+            min_saving: 1,
+            ..PART1_RULES
+        };
+        let histogram = cheat_report(&racetrack, rules, 1).unwrap().histogram;
+        let expected = BTreeMap::from([
+            (2, 14),
+            (4, 14),
+            (6, 2),
+            (8, 4),
+            (10, 2),
+            (12, 3),
+            (20, 1),
+            (36, 1),
+            (38, 1),
+            (40, 1),
+            (64, 1),
+        ]);
+        assert_eq!(histogram, expected);
+    }
+
+    #[test]
+    fn part2_histogram() {
+        let racetrack = Day20::parse(SAMPLE_TEXT).unwrap();
+        let rules = CheatRules {
+            min_saving: 50,
+            ..PART2_RULES
+        };
+        let histogram = cheat_report(&racetrack, rules, 2).unwrap().histogram;
+        let expected = BTreeMap::from([
+            (50, 32),
+            (52, 31),
+            (54, 29),
+            (56, 39),
+            (58, 25),
+            (60, 23),
+            (62, 20),
+            (64, 19),
+            (66, 12),
+            (68, 14),
+            (70, 12),
+            (72, 22),
+            (74, 4),
+            (76, 3),
+        ]);
+        assert_eq!(histogram, expected);
+    }
+
+    #[test]
+    fn report_lists_histogram_when_verbose() {
+        let racetrack = Day20::parse(SAMPLE_TEXT).unwrap();
+        let rules = CheatRules {
+            min_saving: 38,
+            ..PART1_RULES
+        };
+        let report = cheat_report(&racetrack, rules, 1).unwrap();
+        assert_eq!(report.to_string(), "3");
+        let expected = "3
+38 picoseconds saved: 1
+40 picoseconds saved: 1
+64 picoseconds saved: 1";
+        assert_eq!(format!("{report:#}"), expected);
+    }
+}
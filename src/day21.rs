@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::{parse_number, ParseError, ParseErrorKind, Result},
+    geometry::Point,
+    grid::Grid,
+    solution::Solution,
+};
+
+// Directional keypads operated by robots, between the human and the door.
+const PART1_ROBOTS: usize = 2;
+const PART2_ROBOTS: usize = 25;
+
+// ' ' is the gap that no robot arm may ever point at.
+const NUMERIC_LAYOUT: &str = "789\n456\n123\n 0A";
+const DIRECTIONAL_LAYOUT: &str = " ^A\n<v>";
+
+struct Keypad {
+    keys: Grid<char>,
+}
+
+impl Keypad {
+    fn new(layout: &str) -> Self {
+        let keys = Grid::parse(0, layout, Some).expect("keypad layouts should be rectangular");
+        Self { keys }
+    }
+
+    fn find(&self, key: char) -> Point {
+        let (x, y) = self
+            .keys
+            .position(|&k| k == key)
+            .expect("every key that gets pressed should be on the keypad");
+        Point::new(x, y)
+    }
+
+    /*
+    The arrow sequences (each ending with 'A' to press the key) worth trying for
+    moving from `from` to `to`.
+
+    Only two can be the cheapest: all the horizontal moves then all the
+    vertical ones, or the other way around. Zig-zagging costs extra presses on
+    the keypad above, since each change of direction means moving its arm.
+    Either order is dropped if it would sweep over the gap.
+     */
+    fn routes(&self, from: char, to: char) -> Vec<Vec<char>> {
+        let (a, b) = (self.find(from), self.find(to));
+        let gap = self.find(' ');
+        let horizontal = if b.x > a.x { '>' } else { '<' };
+        let vertical = if b.y > a.y { 'v' } else { '^' };
+        let h_moves = vec![horizontal; a.x.abs_diff(b.x)];
+        let v_moves = vec![vertical; a.y.abs_diff(b.y)];
+
+        let mut routes = Vec::new();
+        if Point::new(b.x, a.y) != gap {
+            routes.push([h_moves.clone(), v_moves.clone(), vec!['A']].concat());
+        }
+        if Point::new(a.x, b.y) != gap {
+            routes.push([v_moves, h_moves, vec!['A']].concat());
+        }
+        return routes;
+    }
+}
+
+/*
+The fewest button presses needed through a chain of keypads.
+
+The expensive part of the puzzle is that each sequence typed at one level
+becomes a much longer one on the level above. But every robot arm starts on
+'A', and goes back to 'A' after each key it presses, so the cost of "move from
+key x to key y and press it, with n robots above" never changes. Remembering
+those costs means the sequences themselves never have to be built.
+ */
+struct PressCounter {
+    numeric: Keypad,
+    directional: Keypad,
+    // (from, to, robots above) -> presses
+    memo: HashMap<(char, char, usize), u64>,
+}
+
+impl PressCounter {
+    fn new() -> Self {
+        Self {
+            numeric: Keypad::new(NUMERIC_LAYOUT),
+            directional: Keypad::new(DIRECTIONAL_LAYOUT),
+            memo: HashMap::new(),
+        }
+    }
+
+    // Presses for typing `sequence` on a directional keypad with `robots` above it.
+    fn sequence(&mut self, sequence: &[char], robots: usize) -> u64 {
+        if robots == 0 {
+            // The human types it directly
+            return sequence.len() as u64;
+        }
+        let mut arm = 'A';
+        let mut presses = 0;
+        for &key in sequence {
+            presses += self.directional_key(arm, key, robots);
+            arm = key;
+        }
+        return presses;
+    }
+
+    fn directional_key(&mut self, from: char, to: char, robots: usize) -> u64 {
+        if let Some(&known) = self.memo.get(&(from, to, robots)) {
+            return known;
+        }
+        let best = self
+            .directional
+            .routes(from, to)
+            .iter()
+            .map(|route| self.sequence(route, robots - 1))
+            .min()
+            .unwrap();
+        self.memo.insert((from, to, robots), best);
+        return best;
+    }
+
+    // Presses for typing `code` on the door's numeric keypad.
+    fn code(&mut self, code: &str, robots: usize) -> u64 {
+        let mut arm = 'A';
+        let mut presses = 0;
+        for key in code.chars() {
+            presses += self
+                .numeric
+                .routes(arm, key)
+                .iter()
+                .map(|route| self.sequence(route, robots))
+                .min()
+                .unwrap();
+            arm = key;
+        }
+        return presses;
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DoorCode {
+    keys: String,
+    // The code without the leading zeros and the 'A'
+    value: u64,
+}
+
+fn parse_codes(input: &str) -> Result<Vec<DoorCode>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(line_idx, line)| -> Result<DoorCode> {
+            let digits = line.strip_suffix('A').ok_or_else(|| {
+                let kind = ParseErrorKind::MissingField("`A` at the end of the code");
+                ParseError::end_of_line(Day21::DAY, line_idx, line, kind)
+            })?;
+            // Only digits, or a robot would be sent looking for a key that isn't there
+            if let Some((idx, c)) = digits.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+                let offending = &digits[idx..idx + c.len_utf8()];
+                let kind = ParseErrorKind::UnexpectedChar;
+                return Err(
+                    ParseError::in_line(Day21::DAY, line_idx, line, offending, kind).into(),
+                );
+            }
+            let value = parse_number(Day21::DAY, line_idx, line, digits)?;
+            return Ok(DoorCode {
+                keys: line.to_string(),
+                value,
+            });
+        })
+        .collect()
+}
+
+// Sum of each code's complexity: presses needed times its numeric value.
+fn total_complexity(codes: &[DoorCode], robots: usize) -> u64 {
+    let mut counter = PressCounter::new();
+    codes
+        .iter()
+        .map(|code| counter.code(&code.keys, robots) * code.value)
+        .sum()
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    const DAY: u8 = 21;
+
+    type Input = Vec<DoorCode>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_codes(input)
+    }
+
+    fn part1(codes: &Self::Input) -> Result<u64> {
+        return Ok(total_complexity(codes, PART1_ROBOTS));
+    }
+
+    fn part2(codes: &Self::Input) -> Result<u64> {
+        return Ok(total_complexity(codes, PART2_ROBOTS));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_TEXT: &str = "029A
+980A
+179A
+456A
+379A";
+
+    #[test]
+    fn run_part1_example() {
+        let expected = 126384;
+        let result = Day21::solve_part1(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part2_example() {
+        // The puzzle doesn't give this one
+        let expected = 154115708116294;
+        let result = Day21::solve_part2(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn presses_per_code() {
+        let mut counter = PressCounter::new();
+        let lengths: Vec<u64> = SAMPLE_TEXT
+            .lines()
+            .map(|code| counter.code(code, PART1_ROBOTS))
+            .collect();
+        assert_eq!(lengths, vec![68, 60, 68, 64, 64]);
+    }
+
+    #[test]
+    fn routes_avoid_the_gap() {
+        let numeric = Keypad::new(NUMERIC_LAYOUT);
+        // Going left first from 0 to 1 would cross the gap
+        assert_eq!(numeric.routes('0', '1'), vec![vec!['^', '<', 'A']]);
+        let directional = Keypad::new(DIRECTIONAL_LAYOUT);
+        assert_eq!(directional.routes('A', '<'), vec![vec!['v', '<', '<', 'A']]);
+    }
+
+    #[test]
+    fn keys_off_the_keypad_are_rejected() {
+        let error = Day21::parse("029A\n+12A").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 21 input, line 2, column 1: unexpected character `+`"
+        );
+    }
+
+    #[test]
+    fn fewer_robots_fewer_presses() {
+        // With no robots in between the human types the arrows directly
+        let mut counter = PressCounter::new();
+        assert_eq!(counter.code("029A", 0), "<A^A>^^AvvvA".len() as u64);
+    }
+}
@@ -0,0 +1,175 @@
+use crate::{
+    error::{parse_number, ParseError, ParseErrorKind, Result},
+    solution::Solution,
+};
+
+// Every buyer's secret number changes this many times in a day.
+const DAILY_SECRETS: usize = 2000;
+
+// Price changes run from -9 to +9, so each one fits in a base-19 digit.
+const CHANGE_VALUES: usize = 19;
+const SEQUENCE_COUNT: usize = CHANGE_VALUES.pow(4);
+
+const PRUNE_MODULO: u64 = 16777216;
+
+/*
+A buyer's secret numbers, starting with the one after `secret`:
+1. Mix in (XOR) the secret times 64, then prune (modulo 2^24).
+2. Mix in the secret divided by 32, then prune.
+3. Mix in the secret times 2048, then prune.
+ */
+#[derive(Clone, Copy, Debug)]
+struct Secrets {
+    secret: u64,
+}
+
+impl Iterator for Secrets {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let mut secret = self.secret;
+        secret = ((secret * 64) ^ secret) % PRUNE_MODULO;
+        secret = ((secret / 32) ^ secret) % PRUNE_MODULO;
+        secret = ((secret * 2048) ^ secret) % PRUNE_MODULO;
+        self.secret = secret;
+        return Some(secret);
+    }
+}
+
+// The secrets a buyer goes through in a day, not counting the starting one.
+fn daily_secrets(initial: u64) -> impl Iterator<Item = u64> {
+    Secrets { secret: initial }.take(DAILY_SECRETS)
+}
+
+/*
+Total bananas for the best sequence of four price changes.
+
+The monkey sells to each buyer the first time the sequence shows up in their
+prices. Every buyer's prices are walked once, adding each sequence's price to
+a running total the first time it's seen for that buyer. The four changes are
+packed into one base-19 number, so the totals are a flat array instead of a
+map. `last_buyer` remembers which buyer each sequence was last counted for, so
+it never has to be cleared between buyers.
+ */
+fn best_sequence_total(initial_secrets: &[u64]) -> u64 {
+    let mut totals = vec![0u64; SEQUENCE_COUNT];
+    let mut last_buyer = vec![usize::MAX; SEQUENCE_COUNT];
+    for (buyer, &initial) in initial_secrets.iter().enumerate() {
+        let mut previous_price = initial % 10;
+        let mut sequence = 0;
+        for (idx, secret) in daily_secrets(initial).enumerate() {
+            let price = secret % 10;
+            let change = (price + 9 - previous_price) as usize;
+            previous_price = price;
+            // Slide the window: drop the oldest change, add the newest
+            sequence = (sequence * CHANGE_VALUES + change) % SEQUENCE_COUNT;
+            if idx >= 3 && last_buyer[sequence] != buyer {
+                last_buyer[sequence] = buyer;
+                totals[sequence] += price;
+            }
+        }
+    }
+    return totals.into_iter().max().unwrap_or(0);
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    const DAY: u8 = 22;
+
+    type Input = Vec<u64>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(line_idx, line)| -> Result<u64> {
+                let secret = parse_number(Day22::DAY, line_idx, line, line)?;
+                // Secrets are always pruned, and anything bigger would overflow when mixed
+                if secret >= PRUNE_MODULO {
+                    let kind = ParseErrorKind::InvalidNumber;
+                    return Err(ParseError::in_line(Day22::DAY, line_idx, line, line, kind).into());
+                }
+                return Ok(secret);
+            })
+            .collect()
+    }
+
+    // Sum of every buyer's last secret of the day
+    fn part1(initial_secrets: &Self::Input) -> Result<u64> {
+        let sum = initial_secrets
+            .iter()
+            .filter_map(|&initial| daily_secrets(initial).last())
+            .sum();
+        return Ok(sum);
+    }
+
+    fn part2(initial_secrets: &Self::Input) -> Result<u64> {
+        return Ok(best_sequence_total(initial_secrets));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn run_part1_example() {
+        let expected = 37327623;
+        let result = Day22::solve_part1("1\n10\n100\n2024").unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part2_example() {
+        let expected = 23;
+        let result = Day22::solve_part2("1\n2\n3\n2024").unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn next_ten_secrets() {
+        let secrets: Vec<u64> = Secrets { secret: 123 }.take(10).collect();
+        let expected = vec![
+            15887950, 16495136, 527345, 704524, 1553684, 12683156, 11100544, 12249484, 7753432,
+            5908254,
+        ];
+        assert_eq!(secrets, expected);
+    }
+
+    #[test]
+    fn flat_array_matches_map() {
+        // Same search, keyed by the changes themselves
+        use std::collections::HashMap;
+        let buyers = [123, 456, 789, 2024];
+        let mut totals: HashMap<[i64; 4], u64> = HashMap::new();
+        for initial in buyers {
+            let prices: Vec<i64> = std::iter::once(initial)
+                .chain(daily_secrets(initial))
+                .map(|s| (s % 10) as i64)
+                .collect();
+            let mut seen = HashMap::new();
+            for window in prices.windows(5) {
+                let changes = [1, 2, 3, 4].map(|i| window[i] - window[i - 1]);
+                seen.entry(changes).or_insert(window[4] as u64);
+            }
+            for (changes, price) in seen {
+                *totals.entry(changes).or_insert(0) += price;
+            }
+        }
+        let expected = totals.into_values().max().unwrap();
+        assert_eq!(best_sequence_total(&buyers), expected);
+    }
+
+    #[test]
+    fn unpruned_secret_is_rejected() {
+        assert!(Day22::parse("16777215").is_ok());
+        let error = Day22::parse("1\n18446744073709551615").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 22 input, line 2, column 1: expected a number, found `18446744073709551615`"
+        );
+    }
+}
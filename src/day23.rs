@@ -0,0 +1,230 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    error::{ParseError, ParseErrorKind, Result},
+    solution::Solution,
+};
+
+/*
+The LAN as an undirected graph. Computers are numbered in the order they first
+show up, and `links[n]` holds every computer directly connected to number `n`.
+ */
+#[derive(Debug)]
+pub struct Network {
+    names: Vec<String>,
+    links: Vec<HashSet<usize>>,
+}
+
+impl Network {
+    fn parse(input: &str) -> Result<Self> {
+        let mut network = Network {
+            names: Vec::new(),
+            links: Vec::new(),
+        };
+        let mut ids: HashMap<&str, usize> = HashMap::new();
+        for (line_idx, line) in input.lines().enumerate() {
+            let (a, b) = line.split_once('-').ok_or_else(|| {
+                let kind = ParseErrorKind::MissingField("second computer after `-`");
+                ParseError::end_of_line(Day23::DAY, line_idx, line, kind)
+            })?;
+            // A computer linked to itself would count as its own neighbour
+            if a == b {
+                let kind = ParseErrorKind::MissingField("a different computer after `-`");
+                return Err(ParseError::in_line(Day23::DAY, line_idx, line, b, kind).into());
+            }
+            let mut id = |name| -> usize {
+                *ids.entry(name).or_insert_with(|| {
+                    network.names.push(name.to_string());
+                    network.links.push(HashSet::new());
+                    network.names.len() - 1
+                })
+            };
+            let (a, b) = (id(a), id(b));
+            network.links[a].insert(b);
+            network.links[b].insert(a);
+        }
+        Ok(network)
+    }
+
+    /*
+    Every set of three computers all linked to each other. Each triangle is
+    found once, from its lowest-numbered computer.
+     */
+    fn triangles(&self) -> impl Iterator<Item = [usize; 3]> + '_ {
+        (0..self.names.len()).flat_map(move |a| {
+            self.links[a]
+                .iter()
+                .filter(move |&&b| b > a)
+                .flat_map(move |&b| {
+                    self.links[a]
+                        .intersection(&self.links[b])
+                        .filter(move |&&c| c > b)
+                        .map(move |&c| [a, b, c])
+                })
+        })
+    }
+
+    /*
+    Bron–Kerbosch with pivoting, reporting the largest clique found.
+
+    `clique` is the clique being built, `candidates` the computers that could
+    still join it, and `excluded` those that could but have already been tried.
+    Any maximal clique has to include the pivot or one of its non-neighbours,
+    so only those need branching on.
+     */
+    fn largest_clique(
+        &self,
+        clique: &mut Vec<usize>,
+        mut candidates: HashSet<usize>,
+        mut excluded: HashSet<usize>,
+        best: &mut Vec<usize>,
+    ) {
+        if candidates.is_empty() && excluded.is_empty() {
+            if clique.len() > best.len() {
+                *best = clique.clone();
+            }
+            return;
+        }
+        let pivot = *candidates
+            .union(&excluded)
+            .max_by_key(|&&p| self.links[p].intersection(&candidates).count())
+            .unwrap();
+        let branches: Vec<usize> = candidates.difference(&self.links[pivot]).copied().collect();
+        for computer in branches {
+            let neighbours = &self.links[computer];
+            clique.push(computer);
+            self.largest_clique(
+                clique,
+                candidates.intersection(neighbours).copied().collect(),
+                excluded.intersection(neighbours).copied().collect(),
+                best,
+            );
+            clique.pop();
+            candidates.remove(&computer);
+            excluded.insert(computer);
+        }
+    }
+
+    // The names in a group of computers, sorted and separated by commas.
+    fn password(&self, computers: &[usize]) -> String {
+        let mut names: Vec<&str> = computers.iter().map(|&c| self.names[c].as_str()).collect();
+        names.sort();
+        return names.join(",");
+    }
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    const DAY: u8 = 23;
+
+    type Input = Network;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        Network::parse(input)
+    }
+
+    // Triangles with at least one computer whose name starts with 't'
+    fn part1(network: &Self::Input) -> Result<usize> {
+        let count = network
+            .triangles()
+            .filter(|triangle| triangle.iter().any(|&c| network.names[c].starts_with('t')))
+            .count();
+        return Ok(count);
+    }
+
+    // The LAN party is the largest group of computers all linked to each other
+    fn part2(network: &Self::Input) -> Result<String> {
+        let mut best = Vec::new();
+        let everyone = (0..network.names.len()).collect();
+        network.largest_clique(&mut Vec::new(), everyone, HashSet::new(), &mut best);
+        return Ok(network.password(&best));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_TEXT: &str = "kh-tc
+qp-kh
+de-cg
+ka-co
+yn-aq
+qp-ub
+cg-tb
+vc-aq
+tb-ka
+wh-tc
+yn-cg
+kh-ub
+ta-co
+de-co
+tc-td
+tb-wq
+wh-td
+ta-ka
+td-qp
+aq-cg
+wq-ub
+ub-vc
+de-ta
+wq-aq
+wq-vc
+wh-yn
+ka-de
+kh-ta
+co-tc
+wh-qp
+tb-vc
+td-yn";
+
+    #[test]
+    fn run_part1_example() {
+        let expected = 7;
+        let result = Day23::solve_part1(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part2_example() {
+        let expected = "co,de,ka,ta";
+        let result = Day23::solve_part2(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn every_triangle_once() {
+        let network = Day23::parse(SAMPLE_TEXT).unwrap();
+        let mut triangles: Vec<String> = network
+            .triangles()
+            .map(|triangle| network.password(&triangle))
+            .collect();
+        triangles.sort();
+        let expected = [
+            "aq,cg,yn", "aq,vc,wq", "co,de,ka", "co,de,ta", "co,ka,ta", "de,ka,ta", "kh,qp,ub",
+            "qp,td,wh", "tb,vc,wq", "tc,td,wh", "td,wh,yn", "ub,vc,wq",
+        ];
+        assert_eq!(triangles, expected);
+    }
+
+    #[test]
+    fn missing_link_is_reported() {
+        let error = Day23::parse("kh-tc\nqpkh").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 23 input, line 2, column 5: missing second computer after `-`"
+        );
+    }
+
+    #[test]
+    fn self_link_is_reported() {
+        let error = Day23::parse("aa-aa\naa-bb").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 23 input, line 1, column 4: missing a different computer after `-`"
+        );
+    }
+}
@@ -1,94 +1,127 @@
-pub fn process_d3p1(input: &str) -> i32 {
-    let mut sum: i32 = 0;
-    let mut scan_location = input;
-    loop {
-        if let Ok((remainder, value)) = try_consume_instruction(scan_location) {
-            // parsing has succeeded in finding a mul(x,y) instruction!
-            // 1: Advance scan location forward to the remaining text
-            scan_location = remainder;
-            // 2: Multiply & accumulate the numbers
-            sum += value;
-        } else {
-            // if no "mul" keyword was found, advance pointer
-            // guard against reaching the end of the pointer.
-            if scan_location.len() > 0 {
-                scan_location = &scan_location[1..];
-            } else {
-                break;
-            }
-        }
+use crate::{error, solution::Solution};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+
+    // The scanner works straight from the text, so there's nothing to parse.
+    type Input = String;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> error::Result<Self::Input> {
+        Ok(input.to_string())
     }
-    return sum;
-}
 
-pub fn process_d3p2(input: &str) -> i32 {
-    let mut sum = 0i32;
-    let mut execute_enable = true;
-    let mut scan_location = input;
-    loop {
-        // Try to find a mul instruction
-
-        // 1. Find "mul"...
-        //  ... and then the rest of the instruction.
-        //      true:   accumulate value (remember the do() and don't() toggle!)
-        //      false:  advance pointer and continue
-        //          ... since "mul" was found, this can't be a "do" or "don't" section, so advancing pointer is safe
-        // 2. find "don't"
-        //      Try for longer word before shorter so we don't get a false match
-        // 3. find "do"
-        // 4. advance pointer and try again
-        let mut needs_advancement: i32 = 3;
-        let maybe_mul = try_consume_instruction(scan_location);
-        match maybe_mul {
-            /* if successful...
-               1. update slice bounds
-               2. accumulate value according to the enablement flag
-            */
-            Ok((remainder, value)) => {
+    fn part1(input: &Self::Input) -> error::Result<i64> {
+        let mut sum: i64 = 0;
+        let mut scan_location = input.as_str();
+        loop {
+            if let Ok((remainder, value)) = try_consume_instruction(scan_location) {
+                // parsing has succeeded in finding a mul(x,y) instruction!
+                // 1: Advance scan location forward to the remaining text
                 scan_location = remainder;
-                if execute_enable {
-                    sum += value;
+                // 2: Multiply & accumulate the numbers
+                sum = add_product(sum, value, 1)?;
+            } else {
+                // if no "mul" keyword was found, advance pointer
+                // guard against reaching the end of the pointer.
+                if scan_location.len() > 0 {
+                    scan_location = skip_char(scan_location);
+                } else {
+                    break;
                 }
             }
-            // When we see "::EndOfText", stop the machine. There's no more to read.
-            Err(ParseError::EndOfText) => break,
-            Err(_) => {
-                needs_advancement -= 1;
-            }
         }
+        return Ok(sum);
+    }
 
-        let maybe_dont = try_consume_dont(scan_location);
-        match maybe_dont {
-            Ok(remainder) => {
-                scan_location = remainder;
-                execute_enable = false;
-            }
-            Err(ParseError::EndOfText) => break,
-            Err(_) => {
-                needs_advancement -= 1;
+    fn part2(input: &Self::Input) -> error::Result<i64> {
+        let mut sum = 0i64;
+        let mut execute_enable = true;
+        let mut scan_location = input.as_str();
+        loop {
+            // Try to find a mul instruction
+
+            // 1. Find "mul"...
+            //  ... and then the rest of the instruction.
+            //      true:   accumulate value (remember the do() and don't() toggle!)
+            //      false:  advance pointer and continue
+            //          ... since "mul" was found, this can't be a "do" or "don't" section, so advancing pointer is safe
+            // 2. find "don't"
+            //      Try for longer word before shorter so we don't get a false match
+            // 3. find "do"
+            // 4. advance pointer and try again
+            let mut needs_advancement: i32 = 3;
+            let maybe_mul = try_consume_instruction(scan_location);
+            match maybe_mul {
+                /* if successful...
+                   1. update slice bounds
+                   2. accumulate value according to the enablement flag
+                */
+                Ok((remainder, value)) => {
+                    scan_location = remainder;
+                    if execute_enable {
+                        sum = add_product(sum, value, 2)?;
+                    }
+                }
+                // When we see "::EndOfText", stop the machine. There's no more to read.
+                Err(ParseError::EndOfText) => break,
+                Err(_) => {
+                    needs_advancement -= 1;
+                }
             }
-        }
 
-        let maybe_do = try_consume_do(scan_location);
-        match maybe_do {
-            Ok(remainder) => {
-                scan_location = remainder;
-                execute_enable = true;
+            let maybe_dont = try_consume_dont(scan_location);
+            match maybe_dont {
+                Ok(remainder) => {
+                    scan_location = remainder;
+                    execute_enable = false;
+                }
+                Err(ParseError::EndOfText) => break,
+                Err(_) => {
+                    needs_advancement -= 1;
+                }
             }
-            Err(ParseError::EndOfText) => break,
-            Err(_) => {
-                needs_advancement -= 1;
+
+            let maybe_do = try_consume_do(scan_location);
+            match maybe_do {
+                Ok(remainder) => {
+                    scan_location = remainder;
+                    execute_enable = true;
+                }
+                Err(ParseError::EndOfText) => break,
+                Err(_) => {
+                    needs_advancement -= 1;
+                }
             }
-        }
-        if needs_advancement == 0 {
-            if scan_location.len() > 0 {
-                scan_location = &scan_location[1..];
-            } else {
-                break;
+            if needs_advancement == 0 {
+                if scan_location.len() > 0 {
+                    scan_location = skip_char(scan_location);
+                } else {
+                    break;
+                }
             }
         }
+        return Ok(sum);
     }
-    return sum;
+}
+
+// Drop the first character, however many bytes it takes up.
+fn skip_char(input: &str) -> &str {
+    let width = input.chars().next().map_or(0, char::len_utf8);
+    return &input[width..];
+}
+
+// Pasted-in garbage can hold products big enough to overflow the running total.
+fn add_product(sum: i64, value: i64, part: u8) -> error::Result<i64> {
+    sum.checked_add(value)
+        .ok_or_else(|| error::Error::NoSolution {
+            day: Day3::DAY,
+            part,
+            reason: "the sum of the products doesn't fit in 64 bits".to_string(),
+        })
 }
 
 fn try_find_tag<'input>(input: &'input str, tag: &'input str) -> Result<&'input str, ParseError> {
@@ -130,7 +163,7 @@ fn try_consume_dont(input: &str) -> Result<&str, ParseError> {
     return Ok(remainder);
 }
 
-fn try_consume_instruction(input: &str) -> Result<(&str, i32), ParseError> {
+fn try_consume_instruction(input: &str) -> Result<(&str, i64), ParseError> {
     // Match the "mul(" tag
     let remainder = try_find_mul(input)?;
     // Get remaining input and digits, or else advance slice by 1 and try again.
@@ -142,10 +175,14 @@ fn try_consume_instruction(input: &str) -> Result<(&str, i32), ParseError> {
     // get the closing parenthesis, or else advance slice
     let remainder = try_consume_closeparen(remainder)?;
 
-    Ok((&remainder, first_digits * second_digits))
+    // A product too big to hold can't be a real instruction either
+    let product = first_digits
+        .checked_mul(second_digits)
+        .ok_or(ParseError::NumberTooLarge)?;
+    Ok((&remainder, product))
 }
 
-fn try_collect_digits(input: &str) -> Result<(&str, i32), ParseError> {
+fn try_collect_digits(input: &str) -> Result<(&str, i64), ParseError> {
     let mut char_iter = input.chars();
     let mut end_idx: usize = 0;
 
@@ -159,11 +196,11 @@ fn try_collect_digits(input: &str) -> Result<(&str, i32), ParseError> {
 
     // An ending index > 0 means we found some digits.
     if end_idx > 0 {
-        // characters must be digits. The unsuccessful parse() path must be unreachable.
-        let number = &input[..end_idx]
-            .parse::<i32>()
-            .expect("String-to-integer conversion failed. This should be unreachable!");
-        Ok((&input[end_idx..], *number))
+        // characters must be digits, so the only way parse() fails is a number too big to hold.
+        let number = input[..end_idx]
+            .parse::<i64>()
+            .map_err(|_| ParseError::NumberTooLarge)?;
+        Ok((&input[end_idx..], number))
     } else {
         // `end_idx == 0` means there were no numbers. Return an error indicating this.
         Err(ParseError::NoNumber)
@@ -209,6 +246,7 @@ enum ParseError {
     // MissingOpenParen isn't here because the match is part of the "mul(" pattern
     MissingCloseParen,
     NoNumber,
+    NumberTooLarge,
     NoComma,
     EndOfText,
 }
@@ -228,12 +266,12 @@ mod test {
     #[test]
     fn run_part1_real() {
         let expected = 157621318;
-        let result = process_d3p1(input_constants::DAY3);
+        let result = Day3::solve_part1(input_constants::DAY3).unwrap();
         assert_eq!(result, expected);
     }
     #[test]
     fn run_part1_example() {
-        let result = process_d3p1(SAMPLE_TEXT);
+        let result = Day3::solve_part1(SAMPLE_TEXT).unwrap();
         eprintln!("Got result: {result}");
         assert_eq!(result, 161);
     }
@@ -241,13 +279,13 @@ mod test {
     #[test]
     fn run_part2_real() {
         let expected = 79845780;
-        let result = process_d3p2(input_constants::DAY3);
+        let result = Day3::solve_part2(input_constants::DAY3).unwrap();
         assert_eq!(result, expected);
     }
 
     #[test]
     fn run_part2_example() {
-        let result = process_d3p2(SAMPLE_TEXT_2);
+        let result = Day3::solve_part2(SAMPLE_TEXT_2).unwrap();
         eprintln!("Got result: {result}");
         assert_eq!(result, 48);
     }
@@ -257,7 +295,7 @@ mod test {
     fn test_part2_doubledouble() {
         let input = "mul(2,2)mul(5,5)";
         let expected = 29;
-        let result = process_d3p2(input);
+        let result = Day3::solve_part2(input).unwrap();
         assert_eq!(result, expected);
     }
 
@@ -300,6 +338,25 @@ mod test {
         assert_eq!(result.unwrap(), expected);
     }
 
+    #[test]
+    fn find_numbers_too_large() {
+        let input = "99999999999999999999)";
+        let result = try_collect_digits(input);
+        assert_eq!(result.unwrap_err(), ParseError::NumberTooLarge);
+    }
+
+    #[test]
+    fn bad_pastes_are_skipped() {
+        // Multi-byte characters, and numbers or products too big to be real
+        let input = "xmul(2,4)émul(3,3)mul(99999999999999999999,2)mul(3037000500,3037000500)";
+        assert_eq!(Day3::solve_part1(input).unwrap(), 17);
+        assert_eq!(Day3::solve_part2(input).unwrap(), 17);
+        assert_eq!(Day3::solve_part1("mul(99999,99999)").unwrap(), 9999800001);
+        // Each product fits, but their sum doesn't
+        let huge = "mul(3037000499,3037000499)".repeat(2);
+        assert!(Day3::solve_part1(&huge).is_err());
+    }
+
     #[test]
     fn find_numbers_leading_text() {
         let input = "this will fail 1337 times";
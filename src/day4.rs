@@ -1,114 +1,72 @@
-use itertools::Itertools;
-
-pub fn process_d4p1(input: &str) -> i32 {
-    let grid = Grid::from(input);
-    let point_iter = (0..grid.height()).cartesian_product(0..grid.width());
-    let dir_list = [
-        (1, 0),
-        (1, 1),
-        (0, 1),
-        (-1, 1),
-        (-1, 0),
-        (-1, -1),
-        (0, -1),
-        (1, -1),
-    ];
-    let mut count = 0;
-    for (y, x) in point_iter {
-        // for each coord, test each direction
-        for dir in dir_list {
-            if check_direction(&grid, (x, y), dir, "XMAS".chars()) {
-                // eprintln!("Matched at ({}, {}) going ({}, {})", x, y, dir.0, dir.1);
-                count += 1;
-            }
-        }
-    }
+use crate::{
+    error::Result,
+    geometry::{Dir8, Point, Vector},
+    grid::Grid,
+    solution::Solution,
+};
 
-    return count;
-}
+pub struct Day4;
 
-pub fn process_d4p2(input: &str) -> i32 {
-    let grid = Grid::from(input);
-    let point_iter = (0..grid.height()).cartesian_product(0..grid.width());
-    /* X MAS patterns
-    M.S     M.M     S.M     S.S
-    .A.     .A.     .A.     .A.
-    M.S     S.S     S.M     M.M
-     */
-    let patterns = [
-        Grid::from("M.S\n.A.\nM.S"),
-        Grid::from("M.M\n.A.\nS.S"),
-        Grid::from("S.M\n.A.\nS.M"),
-        Grid::from("S.S\n.A.\nM.M"),
-    ];
-    let mut count = 0;
-    for (y, x) in point_iter {
-        for pattern in patterns.iter() {
-            if check_kernel(&grid, (x, y), &pattern) {
-                count += 1;
-            }
-        }
-    }
+impl Solution for Day4 {
+    const DAY: u8 = 4;
 
-    return count;
-}
+    type Input = Grid<char>;
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-#[derive(Debug)]
-struct Grid {
-    width: isize,
-    height: isize,
-    letters: Vec<char>,
-}
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Grid::parse(Day4::DAY, input, Some)?)
+    }
 
-impl Grid {
-    /* Get the character at the given coordinates.
-       Returns a '.' for anything out of bounds.
-    */
-    fn get(&self, x: isize, y: isize) -> char {
-        if x < 0 || x >= self.width || y < 0 || y >= self.height {
-            return '.';
-        } else {
-            let idx = x + y * self.width;
-            if let Some(letter) = self.letters.get(idx as usize) {
-                return *letter;
-            } else {
-                eprintln!("Failed to get letter at idx: {idx}, coords {x}, {y}");
-                eprintln!(
-                    "Vec size is {}, Grid size is ({}, {})",
-                    self.letters.len(),
-                    self.width,
-                    self.height
-                );
-                panic!("uh oh");
+    fn part1(grid: &Self::Input) -> Result<i32> {
+        let mut count = 0;
+        for point in grid.points() {
+            // for each coord, test each direction
+            for dir in Dir8::ALL {
+                if check_direction(grid, point, dir.as_vector(), "XMAS".chars()) {
+                    // eprintln!("Matched at {:?} going {:?}", point, dir);
+                    count += 1;
+                }
             }
         }
-    }
 
-    fn width(&self) -> isize {
-        self.width
+        return Ok(count);
     }
 
-    fn height(&self) -> isize {
-        self.height
+    fn part2(grid: &Self::Input) -> Result<i32> {
+        /* X MAS patterns
+        M.S     M.M     S.M     S.S
+        .A.     .A.     .A.     .A.
+        M.S     S.S     S.M     M.M
+         */
+        let patterns = [
+            pattern("M.S\n.A.\nM.S"),
+            pattern("M.M\n.A.\nS.S"),
+            pattern("S.M\n.A.\nS.M"),
+            pattern("S.S\n.A.\nM.M"),
+        ];
+        let mut count = 0;
+        for point in grid.points() {
+            for pattern in patterns.iter() {
+                if check_kernel(grid, point, pattern) {
+                    count += 1;
+                }
+            }
+        }
+
+        return Ok(count);
     }
 }
 
-impl From<&str> for Grid {
-    fn from(value: &str) -> Self {
-        let width = value.find("\n").unwrap();
-        let height = value.chars().filter(|&c| c == '\n').count() + 1; // +1 because last line has no \n trailing it.
-        Self {
-            width: width as isize,
-            height: height as isize,
-            letters: Vec::from_iter(value.chars().filter(|c| *c != '\n')),
-        }
-    }
+// The X-MAS patterns are hardcoded rectangles, so they can't fail to parse.
+fn pattern(text: &str) -> Grid<char> {
+    Grid::parse(Day4::DAY, text, Some).expect("X-MAS patterns should be rectangular")
 }
 
 /*
 Recursively compare elements of a reference input iterator with points in the grid.
 
-Call function with current (x,y), a delta vector. (E.g.: (1, 0) to scan to the right.)
+Call function with current point, a delta vector. (E.g.: (1, 0) to scan to the right.)
 and an iterator yielding the characters of the search pattern.
 
 Each call will check the input coordinate against the reference iterator.
@@ -120,19 +78,16 @@ Each call will check the input coordinate against the reference iterator.
     Else: return failure.
  */
 fn check_direction(
-    grid: &Grid,
-    current_location: (isize, isize),
-    scan_dir: (isize, isize),
+    grid: &Grid<char>,
+    current_location: Point,
+    scan_dir: Vector,
     mut comparison_iter: impl Iterator<Item = char>,
 ) -> bool {
     // get next reference character. If it is empty, then we've passed all tests. Return true!
     if let Some(char_to_find) = comparison_iter.next() {
-        let char_on_grid = grid.get(current_location.0, current_location.1);
-        if char_on_grid == char_to_find {
-            let next_coord = (
-                current_location.0 + scan_dir.0,
-                current_location.1 + scan_dir.1,
-            );
+        let char_on_grid = grid.at(current_location);
+        if char_on_grid == Some(&char_to_find) {
+            let next_coord = current_location + scan_dir;
             // dig deeper...
             return check_direction(grid, next_coord, scan_dir, comparison_iter);
         } else {
@@ -150,16 +105,15 @@ Iterate pattern grid and compare it against the input grid.
 
 `location` represents the top-left corner to simplify reference frame conversions
  */
-fn check_kernel(grid: &Grid, location: (isize, isize), pattern: &Grid) -> bool {
-    let point_iter = (0..pattern.height()).cartesian_product(0..pattern.width());
-    // (x,y) is pattern space
-    // (u,v) is grid space
-    for (y, x) in point_iter {
-        let (u, v) = (x + location.0, y + location.1);
-        let char_on_grid = grid.get(u, v);
-        let char_on_pattern = pattern.get(x, y);
+fn check_kernel(grid: &Grid<char>, location: Point, pattern: &Grid<char>) -> bool {
+    // `pattern_point` is pattern space
+    // `grid_point` is grid space
+    for pattern_point in pattern.points() {
+        let grid_point = location + (pattern_point - Point::new(0, 0));
+        let char_on_grid = grid.at(grid_point);
+        let char_on_pattern = pattern.at(pattern_point);
         // if chars match (or is the placeholder '.'), partial match. Keep searching.
-        if char_on_grid == char_on_pattern || char_on_pattern == '.' {
+        if char_on_grid == char_on_pattern || char_on_pattern == Some(&'.') {
             continue;
         } else {
             // if they don't match, definite fail.
@@ -189,28 +143,37 @@ MXMXAXMASX";
     #[test]
     fn run_part1_example() {
         let expected = 18;
-        let result = process_d4p1(SAMPLE_TEXT);
+        let result = Day4::solve_part1(SAMPLE_TEXT).unwrap();
         assert_eq!(expected, result);
     }
 
+    #[test]
+    fn grid_from_windows_file() {
+        // CRLF endings and a trailing newline, like a file saved on Windows
+        let input = "MES\r\nSAS\r\nMRS\r\n";
+        let grid = Day4::parse(input).unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 3));
+        assert_eq!(grid.get(2, 2), Some(&'S'));
+    }
+
     #[test]
     fn run_part1_real() {
         let expected = 2454;
-        let result = process_d4p1(input_constants::DAY4);
+        let result = Day4::solve_part1(input_constants::DAY4).unwrap();
         assert_eq!(expected, result);
     }
 
     #[test]
     fn run_part2_example() {
         let expected = 9;
-        let result = process_d4p2(SAMPLE_TEXT);
+        let result = Day4::solve_part2(SAMPLE_TEXT).unwrap();
         assert_eq!(expected, result);
     }
 
     #[test]
     fn run_part2_real() {
         let expected = 1858;
-        let result = process_d4p2(input_constants::DAY4);
+        let result = Day4::solve_part2(input_constants::DAY4).unwrap();
         assert_eq!(expected, result);
     }
 
@@ -222,8 +185,8 @@ MXMXAXMASX";
         MRS     M.S
          */
         let input = "MES\nSAS\nMRS";
-        let pattern = Grid::from("M.S\n.A.\nM.S");
-        let result = check_kernel(&Grid::from(input), (0, 0), &pattern);
+        let pattern = pattern("M.S\n.A.\nM.S");
+        let result = check_kernel(&Day4::parse(input).unwrap(), Point::new(0, 0), &pattern);
         assert!(result);
     }
 }
@@ -2,6 +2,11 @@ use std::collections::HashSet;
 
 use itertools::Itertools;
 
+use crate::{
+    error::{parse_number, Error, ParseError, ParseErrorKind, Result},
+    solution::Solution,
+};
+
 /*    Design 1: Scan backwards to see if the number appears where it shouldn't
 
 1. Select active rules based on numbers
@@ -47,43 +52,124 @@ Notes:
     in the RuleSet. To be in the RuleSet, it must have been present in the input list.
  */
 
-// this follows "design 2"
-pub fn process_d5p1(input: &str) -> i32 {
-    let mut parts = input.split("\n\n");
-    let rules: Vec<Rule> = parts
-        .next()
-        .unwrap()
-        .split("\n")
-        .map(|line| {
-            let mut nums = line.split("|");
-            let left = nums.next().unwrap().parse::<i32>().unwrap();
-            let right = nums.next().unwrap().parse::<i32>().unwrap();
-            Rule { left, right }
-        })
-        .collect();
+pub struct Day5;
 
-    let page_sequences: Vec<Vec<i32>> = parts
-        .next()
-        .unwrap()
-        .split("\n")
-        .map(|line| {
-            line.split(",")
-                .map(|number| number.parse::<i32>().unwrap())
-                .collect::<Vec<i32>>()
+// The page ordering rules and the list of page updates to check against them
+#[derive(Debug)]
+pub struct PrintQueue {
+    rules: RuleSet,
+    page_sequences: Vec<Vec<i32>>,
+}
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+
+    type Input = PrintQueue;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        let mut parts = input.split("\n\n");
+        // `split()` always yields at least one item, even for empty input.
+        let rules_text = parts.next().unwrap();
+        let updates_text = parts.next().ok_or_else(|| ParseError {
+            day: Day5::DAY,
+            line: rules_text.lines().count() + 1,
+            column: 1,
+            text: String::new(),
+            kind: ParseErrorKind::MissingSection("page updates after a blank line"),
+        })?;
+
+        let rules: Vec<Rule> = rules_text
+            .lines()
+            .enumerate()
+            .map(|(line_idx, line)| -> Result<Rule> {
+                let mut nums = line.split("|");
+                let left = nums.next().unwrap();
+                let right = nums.next().ok_or_else(|| {
+                    ParseError::end_of_line(
+                        Day5::DAY,
+                        line_idx,
+                        line,
+                        ParseErrorKind::MissingField("`|` and right page number"),
+                    )
+                })?;
+                let left = parse_number(Day5::DAY, line_idx, line, left)?;
+                let right = parse_number(Day5::DAY, line_idx, line, right)?;
+                Ok(Rule { left, right })
+            })
+            .collect::<Result<Vec<Rule>>>()?;
+
+        // Line numbers keep counting through the rules and the blank line between sections
+        let first_update_idx = rules.len() + 1;
+        let page_sequences: Vec<Vec<i32>> = updates_text
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| {
+                line.split(",")
+                    .map(|number| parse_number(Day5::DAY, first_update_idx + idx, line, number))
+                    .collect::<std::result::Result<Vec<i32>, _>>()
+            })
+            .collect::<std::result::Result<Vec<Vec<i32>>, _>>()?;
+
+        Ok(PrintQueue {
+            rules: HashSet::from_iter(rules.into_iter()),
+            page_sequences,
         })
-        .collect();
+    }
 
-    let rules: HashSet<Rule> = HashSet::from_iter(rules.into_iter());
-    let mut sum = 0;
-    for row in page_sequences {
-        // 1. select active rules
-        let active_rules = select_active_rules(&rules, &row);
-        if check_digits(&active_rules, &row) {
-            let middle_value = row.get(row.len() / 2).unwrap();
-            sum += middle_value;
+    // this follows "design 2"
+    fn part1(queue: &Self::Input) -> Result<i32> {
+        let mut sum = 0;
+        for row in queue.page_sequences.iter() {
+            // 1. select active rules
+            let active_rules = select_active_rules(&queue.rules, row);
+            if check_digits(&active_rules, row) {
+                let middle_value = row.get(row.len() / 2).unwrap();
+                sum += middle_value;
+            }
         }
+        return Ok(sum);
+    }
+
+    fn part2(queue: &Self::Input) -> Result<i32> {
+        let mut sum = 0;
+        for row in queue.page_sequences.iter() {
+            let active_rules = select_active_rules(&queue.rules, row);
+            if check_digits(&active_rules, row) {
+                // Already in order. Only the broken ones count for part 2.
+                continue;
+            }
+            let fixed = reorder(&active_rules, row).ok_or_else(|| Error::NoSolution {
+                day: Day5::DAY,
+                part: 2,
+                reason: format!("the rules for update {row:?} go around in a circle"),
+            })?;
+            sum += fixed[fixed.len() / 2];
+        }
+        return Ok(sum);
     }
-    return sum;
+}
+
+/*
+Put a row in an order that satisfies all of the (active) rules.
+
+This is a topological sort: repeatedly take the first page that no other
+remaining page has to come before. Returns None when the rules form a cycle,
+because then no page is ever free to go next.
+ */
+fn reorder(rules: &RuleSet, row: &[i32]) -> Option<Vec<i32>> {
+    let mut remaining = row.to_vec();
+    let mut ordered = Vec::with_capacity(row.len());
+    while !remaining.is_empty() {
+        let next_idx = remaining.iter().position(|&page| {
+            !remaining
+                .iter()
+                .any(|&other| rules.contains(&Rule::new(other, page)))
+        })?;
+        ordered.push(remaining.remove(next_idx));
+    }
+    Some(ordered)
 }
 
 fn check_digits(rules: &RuleSet, row: &Vec<i32>) -> bool {
@@ -195,7 +281,7 @@ mod test {
 
     #[test]
     fn run_part1_real() {
-        assert_eq!(6505, process_d5p1(input_constants::DAY5))
+        assert_eq!(6505, Day5::solve_part1(input_constants::DAY5).unwrap())
     }
 
     const SAMPLE_TEXT: &str = "47|53
@@ -254,10 +340,66 @@ mod test {
     #[test]
     fn run_part1_example() {
         let expected = 143;
-        let result = process_d5p1(SAMPLE_TEXT);
+        let result = Day5::solve_part1(SAMPLE_TEXT).unwrap();
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn run_part1_example_crlf() {
+        let input = SAMPLE_TEXT.replace("\n", "\r\n") + "\r\n";
+        assert_eq!(Day5::solve_part1(&input).unwrap(), 143);
+    }
+
+    #[test]
+    fn run_part2_real() {
+        assert_eq!(6897, Day5::solve_part2(input_constants::DAY5).unwrap())
+    }
+
+    #[test]
+    fn run_part2_example() {
+        let expected = 123;
+        let result = Day5::solve_part2(SAMPLE_TEXT).unwrap();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn reorder_bad_sequences() {
+        let rules: RuleSet = ALL_EXAMPLE_RULES.into();
+        let cases = [
+            (vec![75, 97, 47, 61, 53], vec![97, 75, 47, 61, 53]),
+            (vec![61, 13, 29], vec![61, 29, 13]),
+            (vec![97, 13, 75, 29, 47], vec![97, 75, 47, 29, 13]),
+        ];
+        for (row, expected) in cases {
+            let active_rules = select_active_rules(&rules, &row);
+            assert_eq!(reorder(&active_rules, &row), Some(expected));
+        }
+    }
+
+    #[test]
+    fn reorder_cycle_is_unsolvable() {
+        let rules: RuleSet = HashSet::from([Rule::new(1, 2), Rule::new(2, 3), Rule::new(3, 1)]);
+        assert_eq!(reorder(&rules, &[1, 2, 3]), None);
+    }
+
+    #[test]
+    fn parse_bad_page_number() {
+        let error = Day5::parse("47|53\n97|13\n\n75,47,6l,53").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 5 input, line 4, column 7: expected a number, found `6l`"
+        );
+    }
+
+    #[test]
+    fn parse_missing_updates() {
+        let error = Day5::parse("47|53\n97|13").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 5 input, line 3, column 1: missing page updates after a blank line"
+        );
+    }
+
     #[test]
     fn test_rule_selector() {
         let expected: RuleSet = HashSet::from_iter(
@@ -1,99 +1,109 @@
-pub fn process_d6p1(input: &str) -> i32 {
-    let (mut guard, mut board) = parse(input);
+use std::collections::HashSet;
 
-    // Wow, this is dumb.
-    // Loop until we leave the board.
-    while step_guard(&mut guard, &mut board) {
-        continue;
-    }
+use crate::{
+    error::{ParseError, ParseErrorKind, Result},
+    geometry::{Dir4, Point},
+    grid::Grid,
+    solution::Solution,
+};
 
-    // iterate the board tiles and count `VisitedSpace`s
-    board
-        .tiles
-        .into_iter()
-        .filter(|tile| tile == &Tile::VisitedSpace)
-        .count() as i32
-}
+pub struct Day6;
 
-pub fn process_d6p2(input: &str) -> i32 {
-    todo!();
-}
+impl Solution for Day6 {
+    const DAY: u8 = 6;
 
-struct Board {
-    tiles: Vec<Tile>,
-    width: isize,
-    height: isize,
-}
+    type Input = (Guard, Grid<Tile>);
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse(input)
+    }
+
+    fn part1((guard, board): &Self::Input) -> Result<i32> {
+        let mut guard = guard.clone();
+        let mut board = board.clone();
 
-impl Board {
-    fn get(&self, x: isize, y: isize) -> Option<&Tile> {
-        if x < 0 || x >= self.width || y < 0 || y > self.height {
-            return None;
+        // Wow, this is dumb.
+        // Loop until we leave the board.
+        while step_guard(&mut guard, &mut board) {
+            continue;
         }
 
-        self.tiles.get(x as usize + (y * self.width) as usize)
+        // iterate the board tiles and count `VisitedSpace`s
+        let visited = board
+            .iter()
+            .filter(|tile| tile == &&Tile::VisitedSpace)
+            .count();
+        Ok(visited as i32)
     }
 
-    fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut Tile> {
-        if x < 0 || x >= self.width || y < 0 || y > self.height {
-            return None;
+    fn part2((guard, board): &Self::Input) -> Result<i32> {
+        // A new obstacle only matters if the guard would have walked into it,
+        // so the candidates are the tiles on the part 1 route.
+        let mut route = board.clone();
+        let mut walker = guard.clone();
+        while step_guard(&mut walker, &mut route) {
+            continue;
         }
 
-        self.tiles.get_mut(x as usize + (y * self.width) as usize)
+        // Place each obstacle on a scratch board and put the tile back afterwards.
+        // The guard leaves VisitedSpace tiles behind, but those walk the same as Space.
+        let mut scratch = board.clone();
+        let mut count = 0;
+        for candidate in route.points() {
+            // The guard is standing at the start, so no obstacle can go there.
+            if candidate == guard.pos || route.at(candidate) != Some(&Tile::VisitedSpace) {
+                continue;
+            }
+            let original = scratch.at(candidate).cloned().unwrap();
+            *scratch.at_mut(candidate).unwrap() = Tile::Obstacle;
+            if guard_loops(guard.clone(), &mut scratch) {
+                count += 1;
+            }
+            *scratch.at_mut(candidate).unwrap() = original;
+        }
+        Ok(count)
     }
 }
 
-fn parse(input: &str) -> (Guard, Board) {
-    let width = input.find("\n").unwrap() as isize;
-    let height = input.chars().filter(|&c| c == '\n').count() as isize + 1; // +1 because last line has no newline trailer
-    let mut guard = Guard {
-        pos: (0, 0),
-        facing: Facing::Up,
+fn parse(input: &str) -> Result<(Guard, Grid<Tile>)> {
+    let board = Grid::parse(Day6::DAY, input, |c| match c {
+        '.' => Some(Tile::Space),    // this is a space
+        '#' => Some(Tile::Obstacle), // this is an obstacle
+        // this is the guard, but the guard is *on* a space.
+        '^' => Some(Tile::VisitedSpace),
+        _ => None,
+    })?;
+
+    // The only tile visited so far is the one the guard is standing on.
+    let pos = board
+        .position(|tile| tile == &Tile::VisitedSpace)
+        .ok_or_else(|| ParseError {
+            day: Day6::DAY,
+            line: 1,
+            column: 1,
+            text: String::new(),
+            kind: ParseErrorKind::MissingSection("the guard `^`"),
+        })?;
+    let guard = Guard {
+        pos: Point::from(pos),
+        facing: Dir4::Up,
     };
-    let tiles = input
-        .chars()
-        .filter(|&c| c != '\n')
-        .enumerate()
-        .map(|(idx, c)| {
-            match c {
-                '.' => Tile::Space,    // this is a space
-                '#' => Tile::Obstacle, // this is an obstacle
-                '^' => {
-                    // this is the guard, but the guard is *on* a space.
-                    guard = Guard {
-                        pos: (idx as isize % width, idx as isize / width),
-                        facing: Facing::Up,
-                    };
-                    Tile::VisitedSpace
-                }
-                _ => panic!("Unexpected character in input string! Char: {}", c as i32),
-            }
-        })
-        .collect();
-    return (
-        guard,
-        Board {
-            tiles,
-            width,
-            height,
-        },
-    );
+    return Ok((guard, board));
 }
 
-#[derive(Default)]
-struct Guard {
-    pos: (isize, isize),
-    facing: Facing,
+#[derive(Clone, Debug)]
+pub struct Guard {
+    pos: Point,
+    facing: Dir4,
 }
 
 // Some(bool) indicates that the guard moved. The bool indicates if it is still on the board.
-fn step_guard(guard: &mut Guard, board: &mut Board) -> bool {
+fn step_guard(guard: &mut Guard, board: &mut Grid<Tile>) -> bool {
     // get the step direction, then the tile at that spot.
-    let next_pos = (
-        guard.pos.0 + guard.facing.as_delta().0,
-        guard.pos.1 + guard.facing.as_delta().1,
-    );
-    if let Some(next_tile) = board.get_mut(next_pos.0, next_pos.1) {
+    let next_pos = guard.pos + guard.facing.as_vector();
+    if let Some(next_tile) = board.at_mut(next_pos) {
         // if there's a tile here, see if we can move
         match next_tile {
             Tile::Space => {
@@ -107,7 +117,7 @@ fn step_guard(guard: &mut Guard, board: &mut Board) -> bool {
             }
             Tile::Obstacle => {
                 // obstacle. Rotate and stay put.
-                guard.facing = guard.facing.following();
+                guard.facing = guard.facing.clockwise();
             }
         }
         // tile was Some(_), so we're on the board. return true
@@ -118,44 +128,33 @@ fn step_guard(guard: &mut Guard, board: &mut Board) -> bool {
     }
 }
 
+/*
+Walk the guard until it either leaves the board (false) or gets stuck in a loop (true).
+
+A loop means the guard turns at the same spot, facing the same way, twice. Only
+the turns are recorded, which keeps the set much smaller than every step would.
+ */
+fn guard_loops(mut guard: Guard, board: &mut Grid<Tile>) -> bool {
+    let mut turns: HashSet<(Point, Dir4)> = HashSet::new();
+    loop {
+        let facing = guard.facing;
+        if !step_guard(&mut guard, board) {
+            return false;
+        }
+        if guard.facing != facing && !turns.insert((guard.pos, facing)) {
+            return true;
+        }
+    }
+}
+
 // I'm anticipating additional tile types in part 2S
-#[derive(PartialEq)]
-enum Tile {
+#[derive(Clone, Debug, PartialEq)]
+pub enum Tile {
     Space,        // empty space
     VisitedSpace, // space the guard has visited
     Obstacle,
 }
 
-#[derive(Default)]
-enum Facing {
-    #[default]
-    Right,
-    Up,
-    Left,
-    Down,
-}
-
-impl Facing {
-    // returns the unit vector for the named direction
-    fn as_delta(&self) -> (isize, isize) {
-        match self {
-            Facing::Right => (1, 0),
-            Facing::Up => (0, -1),
-            Facing::Left => (-1, 0),
-            Facing::Down => (0, 1),
-        }
-    }
-
-    fn following(&self) -> Self {
-        match self {
-            Facing::Right => Facing::Down,
-            Facing::Up => Facing::Right,
-            Facing::Left => Facing::Up,
-            Facing::Down => Facing::Left,
-        }
-    }
-}
-
 #[cfg(test)]
 mod test {
 
@@ -165,7 +164,7 @@ mod test {
     #[test]
     fn run_part1_real() {
         let expected = 5242;
-        assert_eq!(process_d6p1(input_constants::DAY6), expected);
+        assert_eq!(Day6::solve_part1(input_constants::DAY6).unwrap(), expected);
     }
 
     const SAMPLE_TEXT: &str = "....#.....
@@ -182,12 +181,50 @@ mod test {
     #[test]
     fn run_part1_example() {
         let expected = 41;
-        let result = process_d6p1(SAMPLE_TEXT);
+        let result = Day6::solve_part1(SAMPLE_TEXT).unwrap();
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn run_part1_example_crlf() {
+        let input = SAMPLE_TEXT.replace("\n", "\r\n") + "\r\n";
+        assert_eq!(Day6::solve_part1(&input).unwrap(), 41);
+    }
+
+    #[test]
+    fn parse_unexpected_char() {
+        let error = Day6::parse("....#\n.^...\n..O..").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 6 input, line 3, column 3: unexpected character `O`"
+        );
+    }
+
+    #[test]
+    fn run_part2_real() {
+        let expected = 1424;
+        assert_eq!(Day6::solve_part2(input_constants::DAY6).unwrap(), expected);
+    }
+
     #[test]
     fn run_part2_example() {
-        todo!("Complete part 1 first. Can't fill out test until we see example.");
+        let expected = 6;
+        let result = Day6::solve_part2(SAMPLE_TEXT).unwrap();
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn test_loop_detection() {
+        /*
+        An obstacle at the end of every leg, so the guard walks in a square forever:
+
+        .#...
+        ....#
+        .^...
+        #....
+        ...#.
+         */
+        let (guard, mut board) = parse(".#...\n....#\n.^...\n#....\n...#.").unwrap();
+        assert!(guard_loops(guard, &mut board));
     }
 }
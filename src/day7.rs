@@ -0,0 +1,199 @@
+use crate::{
+    error::{parse_number, ParseError, ParseErrorKind, Result},
+    solution::Solution,
+};
+
+/*
+One calibration line: a test value and the numbers that should combine into it
+when the right operators are slotted in between.
+ */
+#[derive(Debug, PartialEq)]
+pub struct Equation {
+    target: u64,
+    operands: Vec<u64>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Operator {
+    Add,
+    Multiply,
+    // `||` glues the digits together: 12 || 345 = 12345
+    Concatenate,
+}
+
+impl Operator {
+    /*
+    Undo `lhs <op> rhs = result`, returning the `lhs` that would have produced
+    `result`. `None` if no whole-number `lhs` could have done it.
+     */
+    fn unapply(self, result: u64, rhs: u64) -> Option<u64> {
+        match self {
+            Operator::Add => result.checked_sub(rhs),
+            Operator::Multiply => {
+                if rhs != 0 && result.is_multiple_of(rhs) {
+                    Some(result / rhs)
+                } else {
+                    None
+                }
+            }
+            Operator::Concatenate => {
+                // A 20-digit `rhs` leaves no room for any `lhs` digits in a u64
+                let shift = 10u64.checked_pow(digit_count(rhs))?;
+                if result % shift == rhs && result > rhs {
+                    Some(result / shift)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+}
+
+fn digit_count(mut value: u64) -> u32 {
+    let mut digits = 1;
+    while value >= 10 {
+        value /= 10;
+        digits += 1;
+    }
+    return digits;
+}
+
+impl Equation {
+    /*
+    Operators are evaluated left-to-right, so the last operand is the last one
+    applied. Working backwards from the target prunes most of the search: a
+    multiply only fits when it divides evenly and a concatenation only fits
+    when the target ends in the right digits.
+     */
+    fn can_be_true(&self, operators: &[Operator]) -> bool {
+        return solvable(self.target, &self.operands, operators);
+    }
+}
+
+fn solvable(target: u64, operands: &[u64], operators: &[Operator]) -> bool {
+    match operands {
+        [] => false,
+        [first] => *first == target,
+        [rest @ .., last] => operators.iter().any(|op| {
+            op.unapply(target, *last)
+                .is_some_and(|lhs| solvable(lhs, rest, operators))
+        }),
+    }
+}
+
+fn parse_equations(input: &str) -> Result<Vec<Equation>> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(line_idx, line)| -> Result<Equation> {
+            let (target, operands) = line.split_once(':').ok_or_else(|| {
+                ParseError::end_of_line(
+                    Day7::DAY,
+                    line_idx,
+                    line,
+                    ParseErrorKind::MissingField("`:` after the test value"),
+                )
+            })?;
+            let target = parse_number(Day7::DAY, line_idx, line, target)?;
+            let operands = operands
+                .split_whitespace()
+                .map(|token| parse_number(Day7::DAY, line_idx, line, token))
+                .collect::<std::result::Result<Vec<u64>, ParseError>>()?;
+            if operands.is_empty() {
+                return Err(ParseError::end_of_line(
+                    Day7::DAY,
+                    line_idx,
+                    line,
+                    ParseErrorKind::MissingField("numbers after the `:`"),
+                )
+                .into());
+            }
+            return Ok(Equation { target, operands });
+        })
+        .collect()
+}
+
+fn calibration_result(equations: &[Equation], operators: &[Operator]) -> u64 {
+    equations
+        .iter()
+        .filter(|equation| equation.can_be_true(operators))
+        .map(|equation| equation.target)
+        .sum()
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+
+    type Input = Vec<Equation>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        parse_equations(input)
+    }
+
+    fn part1(equations: &Self::Input) -> Result<u64> {
+        let operators = [Operator::Add, Operator::Multiply];
+        return Ok(calibration_result(equations, &operators));
+    }
+
+    fn part2(equations: &Self::Input) -> Result<u64> {
+        let operators = [Operator::Add, Operator::Multiply, Operator::Concatenate];
+        return Ok(calibration_result(equations, &operators));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_TEXT: &str = "190: 10 19
+3267: 81 40 27
+83: 17 5
+156: 15 6
+7290: 6 8 6 15
+161011: 16 10 13
+192: 17 8 14
+21037: 9 7 18 13
+292: 11 6 16 20";
+
+    #[test]
+    fn run_part1_example() {
+        let expected = 3749;
+        let result = Day7::solve_part1(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part2_example() {
+        let expected = 11387;
+        let result = Day7::solve_part2(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn unapply_concatenation() {
+        assert_eq!(Operator::Concatenate.unapply(12345, 345), Some(12));
+        assert_eq!(Operator::Concatenate.unapply(12345, 45), Some(123));
+        assert_eq!(Operator::Concatenate.unapply(12345, 44), None);
+        // Nothing is left over to concatenate onto
+        assert_eq!(Operator::Concatenate.unapply(345, 345), None);
+    }
+
+    #[test]
+    fn twenty_digit_operand() {
+        let result = Day7::solve_part2("18446744073709551615: 1 10000000000000000000").unwrap();
+        assert_eq!(result, 0);
+    }
+
+    #[test]
+    fn missing_colon_is_reported() {
+        let error = Day7::parse("190: 10 19\n83 17 5").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 7 input, line 2, column 8: missing `:` after the test value"
+        );
+    }
+}
@@ -1,206 +1,93 @@
-use std::ops::{Add, Sub};
-
 use itertools::Itertools;
 
-pub fn process_d8p1(input: &str) -> i32 {
-    let radio_map = Grid::from(input);
-
-    // generate coords with a cartesian product over the width & height
-    // filter all coordinates that are *not* a radio frequency
-    let radio_stations = (0..radio_map.width())
-        .cartesian_product(0..radio_map.height())
-        .filter(|coord| radio_map.get(coord.0, coord.1) != '.');
-
-    // Antinodes are produced by pairs of towers. All pairs of towers is the cartesian_product of the list of towers.
-    let station_pairs = radio_stations.clone().cartesian_product(radio_stations);
-
-    /*
-       filter out self-comparison. A single tower can't produce an antinode
-       filter to match frequencies
-       compute antinode position
-       write antinodes to the antinode_map
-
-    */
-    let antinodes = station_pairs
-        .into_iter()
-        .filter(|(sta, stb)| sta != stb)
-        .filter(|(sta, stb)| radio_map.get(sta.0, sta.1) == radio_map.get(stb.0, stb.1))
-        .map(|(sta, stb)| compute_antinode(Coord::from(sta), Coord::from(stb)));
-
-    let mut antinode_map = Grid::empty(radio_map.width(), radio_map.height());
-    for antinode in antinodes {
-        if let Some(char_in_map) = antinode_map.get_mut(antinode.0, antinode.1) {
-            *char_in_map = '#';
-        } // else, not in the map. Continue iterating
-    }
-
-    // count number of antinodes ('#' symbols in vec)
-    let sum = antinode_map
-        .letters
-        .into_iter()
-        .filter(|letter| letter == &'#')
-        .count();
+use crate::{error::Result, geometry::Point, grid::Grid, solution::Solution};
 
-    return sum as i32;
-}
+pub struct Day8;
 
-pub fn process_d8p2(input: &str) -> i32 {
-    let radio_map = Grid::from(input);
-
-    let radio_stations = (0..radio_map.width())
-        .cartesian_product(0..radio_map.height())
-        .filter(|coord| radio_map.get(coord.0, coord.1) != '.');
-
-    // Station pairs filtered to skip self-comparison and to match frequencies.
-    let station_pairs = radio_stations
-        .clone()
-        .cartesian_product(radio_stations)
-        .filter(|(sta, stb)| sta != stb)
-        .filter(|(sta, stb)| radio_map.get(sta.0, sta.1) == radio_map.get(stb.0, stb.1));
-
-    // Iterate over station pairs and place all antinodes they create.
-    let mut antinode_map = Grid::empty(radio_map.width(), radio_map.height());
-    for (sta, stb) in station_pairs {
-        // Since antinodes are lines drawn through matched pairs of stations, the stations themselves
-        // have an antinode on them...
-        // ...but I didn't make a line drawing algorithm, so I'm doing this instead:
-
-        *antinode_map.get_mut(sta.0, sta.1).unwrap() = '#'; // write antinode on sta
-        *antinode_map.get_mut(stb.0, stb.1).unwrap() = '#'; // and on stb
-
-
-        // As `Coord`s for better naming (and the +/- operators)
-        let sta = Coord::from(sta);
-        let stb = Coord::from(stb);
-        let node_delta = stb - sta;
-
-        // Loop until we exit the map.
-        // for each in-bound tile, place a '#' on it to indicate an antinode.
-        // Advance node_pos by node_delta and continue looping.
-        let mut node_pos: Coord = stb + node_delta;
-        loop {
-            if let Some(char_in_map) = antinode_map.get_mut(node_pos.0, node_pos.1) {
-                // char was in map. Make sure it's an antinode '#' and advance the position marker.
-                *char_in_map = '#';
-                node_pos = node_pos + node_delta;
-            } else {
-                // char was not in map. Break loop, stop placing nodes for this tower pair.
-                break;
-            }
-        }  
-    }
+impl Solution for Day8 {
+    const DAY: u8 = 8;
 
-    let sum = antinode_map.letters.into_iter()
-        .filter(|letter| letter == &'#')
-        .count();
+    type Input = Grid<char>;
+    type Answer1 = i32;
+    type Answer2 = i32;
 
-    return sum as i32;
-}
+    fn parse(input: &str) -> Result<Self::Input> {
+        Ok(Grid::parse(Day8::DAY, input, Some)?)
+    }
 
-#[derive(Debug)]
-struct Grid {
-    width: isize,
-    height: isize,
-    letters: Vec<char>,
-}
+    fn part1(radio_map: &Self::Input) -> Result<i32> {
+        // walk every point of the map
+        // filter all points that are *not* a radio frequency
+        let radio_stations = radio_map
+            .points()
+            .filter(|point| radio_map.at(*point) != Some(&'.'));
 
-impl Grid {
-    fn empty(width: isize, height: isize) -> Self {
-        let mut letters = Vec::new();
-        for _ in 0..(width * height) {
-            letters.push('.')
-        }
+        // Antinodes are produced by pairs of towers. All pairs of towers is the cartesian_product of the list of towers.
+        let station_pairs = radio_stations.clone().cartesian_product(radio_stations);
 
-        Grid {
-            width,
-            height,
-            letters,
-        }
-    }
-    /* Get the character at the given coordinates.
-       Returns a '.' for anything out of bounds.
-    */
-    fn get(&self, x: isize, y: isize) -> char {
-        if x < 0 || x >= self.width || y < 0 || y >= self.height {
-            return '.';
-        } else {
-            let idx = x + y * self.width;
-            if let Some(letter) = self.letters.get(idx as usize) {
-                return *letter;
-            } else {
-                eprintln!("Failed to get letter at idx: {idx}, coords {x}, {y}");
-                eprintln!(
-                    "Vec size is {}, Grid size is ({}, {})",
-                    self.letters.len(),
-                    self.width,
-                    self.height
-                );
-                panic!("uh oh");
-            }
+        /*
+           filter out self-comparison. A single tower can't produce an antinode
+           filter to match frequencies
+           compute antinode position
+           write antinodes to the antinode_map
+
+        */
+        let antinodes = station_pairs
+            .into_iter()
+            .filter(|(sta, stb)| sta != stb)
+            .filter(|(sta, stb)| radio_map.at(*sta) == radio_map.at(*stb))
+            .map(|(sta, stb)| compute_antinode(sta, stb));
+
+        let mut antinode_map = Grid::filled(radio_map.width(), radio_map.height(), '.');
+        for antinode in antinodes {
+            if let Some(char_in_map) = antinode_map.at_mut(antinode) {
+                *char_in_map = '#';
+            } // else, not in the map. Continue iterating
         }
-    }
 
-    fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut char> {
-        if x < 0 || x >= self.width || y < 0 || y >= self.height {
-            None
-        } else {
-            let idx = x + y * self.width;
-            let letters_len = self.letters.len();
-            if let Some(letter) = self.letters.get_mut(idx as usize) {
-                return Some(letter);
-            } else {
-                eprintln!("Failed to get letter at idx: {idx}, coords {x}, {y}");
-                eprintln!(
-                    "Vec size is {}, Grid size is ({}, {})",
-                    letters_len, self.width, self.height
-                );
-                panic!("uh oh");
+        // count number of antinodes ('#' symbols in vec)
+        let sum = antinode_map
+            .iter()
+            .filter(|letter| letter == &&'#')
+            .count();
+
+        return Ok(sum as i32);
+    }
+
+    fn part2(radio_map: &Self::Input) -> Result<i32> {
+        let radio_stations = radio_map
+            .points()
+            .filter(|point| radio_map.at(*point) != Some(&'.'));
+
+        // Station pairs filtered to skip self-comparison and to match frequencies.
+        let station_pairs = radio_stations
+            .clone()
+            .cartesian_product(radio_stations)
+            .filter(|(sta, stb)| sta != stb)
+            .filter(|(sta, stb)| radio_map.at(*sta) == radio_map.at(*stb));
+
+        // Iterate over station pairs and place all antinodes they create.
+        let mut antinode_map = Grid::filled(radio_map.width(), radio_map.height(), '.');
+        for (sta, stb) in station_pairs {
+            // Antinodes are lines drawn through matched pairs of stations, so the stations themselves
+            // have an antinode on them. Walk from sta, through stb, until we exit the map. The reversed
+            // pair walks the line in the other direction.
+            // The reduced delta makes sure no grid point between the two stations gets skipped.
+            let node_delta = (stb - sta).reduced();
+            let mut node_pos = sta;
+            while let Some(char_in_map) = antinode_map.at_mut(node_pos) {
+                // char was in map. Make sure it's an antinode '#' and advance the position marker.
+                *char_in_map = '#';
+                node_pos += node_delta;
             }
         }
-    }
-    fn width(&self) -> isize {
-        self.width
-    }
-
-    fn height(&self) -> isize {
-        self.height
-    }
-}
-
-impl From<&str> for Grid {
-    fn from(value: &str) -> Self {
-        let width = value.find("\n").unwrap();
-        let height = value.chars().filter(|&c| c == '\n').count() + 1; // +1 because last line has no \n trailing it.
-        Self {
-            width: width as isize,
-            height: height as isize,
-            letters: Vec::from_iter(value.chars().filter(|c| *c != '\n')),
-        }
-    }
-}
-
-#[derive(Clone, Copy, Debug, PartialEq)]
-struct Coord(isize, isize);
 
-impl Sub for Coord {
-    type Output = Self;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        Self(self.0 - rhs.0, self.1 - rhs.1)
-    }
-}
-
-impl Add for Coord {
-    type Output = Self;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        Self(self.0 + rhs.0, self.1 + rhs.1)
-    }
-}
+        let sum = antinode_map
+            .iter()
+            .filter(|letter| letter == &&'#')
+            .count();
 
-impl From<(isize, isize)> for Coord {
-    fn from(value: (isize, isize)) -> Self {
-        Self(value.0, value.1)
+        return Ok(sum as i32);
     }
 }
 
@@ -216,11 +103,11 @@ impl From<(isize, isize)> for Coord {
 
    Panics when the points are the same. Violent failure, instead of error handling!
 */
-fn compute_antinode(p1: Coord, p2: Coord) -> Coord {
+fn compute_antinode(p1: Point, p2: Point) -> Point {
     if p1 == p2 {
         panic!("You can't use a single tower to get an antinode!");
     }
-    p2 + p2 - p1 // I don't have `Mul` implemented, so I'm adding
+    p2 + (p2 - p1)
 }
 
 #[cfg(test)]
@@ -231,12 +118,12 @@ mod test {
 
     #[test]
     fn run_part1_real() {
-        assert_eq!(214, process_d8p1(input_constants::DAY8))
+        assert_eq!(214, Day8::solve_part1(input_constants::DAY8).unwrap())
     }
 
     #[test]
     fn run_part2_real() {
-        assert_eq!(809, process_d8p2(input_constants::DAY8))
+        assert_eq!(809, Day8::solve_part2(input_constants::DAY8).unwrap())
     }
 
     const SAMPLE_TEXT: &str = "............
@@ -254,12 +141,18 @@ mod test {
 
     #[test]
     fn run_part1_example() {
-        assert_eq!(process_d8p1(SAMPLE_TEXT), 14)
+        assert_eq!(Day8::solve_part1(SAMPLE_TEXT).unwrap(), 14)
+    }
+
+    #[test]
+    fn run_part1_example_trailing_newline() {
+        let input = format!("{SAMPLE_TEXT}\n");
+        assert_eq!(Day8::solve_part1(&input).unwrap(), 14)
     }
 
     #[test]
     fn run_part2_example() {
-        assert_eq!(process_d8p2(SAMPLE_TEXT), 34);
+        assert_eq!(Day8::solve_part2(SAMPLE_TEXT).unwrap(), 34);
     }
 
     #[test]
@@ -284,7 +177,21 @@ A....
 
         There is 1 node.
          */
-        assert_eq!(1, process_d8p1(input));
+        assert_eq!(1, Day8::solve_part1(input).unwrap());
+    }
+
+    #[test]
+    fn test_part2_nodes_between_stations() {
+        /*
+        The stations are 2 steps apart diagonally, so the line through them
+        also crosses the grid point in between.
+         */
+        let input = "A....
+.....
+..A..
+.....
+.....";
+        assert_eq!(5, Day8::solve_part2(input).unwrap());
     }
 
     #[test]
@@ -300,6 +207,6 @@ A..
          xxxxx
         There are 2 nodes, but only 1 is in bounds.
          */
-        assert_eq!(1, process_d8p1(input));
+        assert_eq!(1, Day8::solve_part1(input).unwrap());
     }
 }
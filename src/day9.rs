@@ -0,0 +1,266 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use crate::{
+    error::{ParseError, ParseErrorKind, Result},
+    solution::Solution,
+};
+
+// A run of blocks on the disk: `len` blocks starting at block `start`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Span {
+    start: usize,
+    len: usize,
+}
+
+/*
+The disk map, expanded into where everything sits on the disk.
+
+`files[id]` is the file with that ID, and `free` holds the gaps in between in
+the order they appear. Spans may be empty (a '0' in the map).
+ */
+#[derive(Debug, PartialEq)]
+pub struct DiskMap {
+    files: Vec<Span>,
+    free: Vec<Span>,
+}
+
+impl DiskMap {
+    /*
+    The dense format alternates file lengths and free-space lengths, one digit
+    each, starting with a file.
+     */
+    fn parse(input: &str) -> Result<Self> {
+        let mut files = Vec::new();
+        let mut free = Vec::new();
+        let mut position = 0;
+        for (line_idx, line) in input.lines().enumerate() {
+            for (idx, c) in line.char_indices() {
+                let len = c.to_digit(10).ok_or_else(|| {
+                    let offending = &line[idx..idx + c.len_utf8()];
+                    let kind = ParseErrorKind::UnexpectedChar;
+                    ParseError::in_line(Day9::DAY, line_idx, line, offending, kind)
+                })? as usize;
+                let span = Span {
+                    start: position,
+                    len,
+                };
+                // files and free spans take turns
+                if files.len() == free.len() {
+                    files.push(span);
+                } else {
+                    free.push(span);
+                }
+                position += len;
+            }
+        }
+        Ok(Self { files, free })
+    }
+
+    // Block-by-block layout: the file ID in each block, or `None` for free space.
+    fn blocks(&self) -> Vec<Option<usize>> {
+        let total = self.files.iter().chain(&self.free).map(|s| s.len).sum();
+        let mut blocks = vec![None; total];
+        for (id, file) in self.files.iter().enumerate() {
+            blocks[file.start..file.start + file.len].fill(Some(id));
+        }
+        return blocks;
+    }
+}
+
+/*
+Move single blocks from the end of the disk into the leftmost free block until
+there are no gaps left. Two cursors meet in the middle, so it's one pass.
+ */
+fn compact_blocks(blocks: &mut [Option<usize>]) {
+    if blocks.is_empty() {
+        return;
+    }
+    let mut left = 0;
+    let mut right = blocks.len() - 1;
+    while left < right {
+        if blocks[left].is_some() {
+            left += 1;
+        } else if blocks[right].is_none() {
+            right -= 1;
+        } else {
+            blocks.swap(left, right);
+        }
+    }
+}
+
+/*
+Free spans bucketed by length. Each bucket is a min-heap of start positions,
+so "the leftmost gap that fits a file of length n" is the smallest top among
+the buckets from n up. There are only a handful of buckets, since one digit
+describes a gap (two or more, if an empty file sits between them).
+ */
+struct FreeIndex {
+    by_len: Vec<BinaryHeap<Reverse<usize>>>,
+}
+
+impl FreeIndex {
+    fn new(spans: &[Span]) -> Self {
+        // Gaps either side of an empty file are really one gap
+        let mut merged: Vec<Span> = Vec::new();
+        for span in spans.iter().filter(|s| s.len > 0) {
+            match merged.last_mut() {
+                Some(last) if last.start + last.len == span.start => last.len += span.len,
+                _ => merged.push(*span),
+            }
+        }
+
+        let longest = merged.iter().map(|s| s.len).max().unwrap_or(0);
+        let mut by_len = vec![BinaryHeap::new(); longest + 1];
+        for span in merged {
+            by_len[span.len].push(Reverse(span.start));
+        }
+        Self { by_len }
+    }
+
+    // Take the leftmost gap of at least `len` blocks that starts before `before`.
+    fn take(&mut self, len: usize, before: usize) -> Option<usize> {
+        let (gap_len, Reverse(start)) = (len..self.by_len.len())
+            .filter_map(|gap_len| self.by_len[gap_len].peek().map(|&top| (gap_len, top)))
+            .min_by_key(|&(_, Reverse(start))| start)?;
+        if start >= before {
+            return None;
+        }
+        self.by_len[gap_len].pop();
+        // whatever the file doesn't fill is still free
+        let left_over = gap_len - len;
+        if left_over > 0 {
+            self.by_len[left_over].push(Reverse(start + len));
+        }
+        return Some(start);
+    }
+}
+
+/*
+Move whole files, highest ID first, into the leftmost gap they fit in. Files
+only ever move left, and each file is tried once.
+ */
+fn compact_files(disk: &DiskMap) -> Vec<Span> {
+    let mut index = FreeIndex::new(&disk.free);
+    let mut files = disk.files.clone();
+    for file in files.iter_mut().rev().filter(|f| f.len > 0) {
+        if let Some(start) = index.take(file.len, file.start) {
+            // The space the file leaves behind is never used: every file
+            // that could move there has a lower ID, and is to the left of it.
+            file.start = start;
+        }
+    }
+    return files;
+}
+
+fn checksum(files: &[Span]) -> usize {
+    files
+        .iter()
+        .enumerate()
+        .map(|(id, file)| (file.start..file.start + file.len).sum::<usize>() * id)
+        .sum()
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+
+    type Input = DiskMap;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input> {
+        DiskMap::parse(input)
+    }
+
+    fn part1(disk: &Self::Input) -> Result<usize> {
+        let mut blocks = disk.blocks();
+        compact_blocks(&mut blocks);
+        let sum = blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(position, id)| id.map(|id| position * id))
+            .sum();
+        return Ok(sum);
+    }
+
+    fn part2(disk: &Self::Input) -> Result<usize> {
+        return Ok(checksum(&compact_files(disk)));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_TEXT: &str = "2333133121414131402";
+
+    // Render blocks the way the puzzle text draws them
+    fn render(blocks: &[Option<usize>]) -> String {
+        blocks
+            .iter()
+            .map(|block| match block {
+                Some(id) => char::from_digit(*id as u32, 10).unwrap(),
+                None => '.',
+            })
+            .collect()
+    }
+
+    #[test]
+    fn run_part1_example() {
+        let expected = 1928;
+        let result = Day9::solve_part1(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn run_part2_example() {
+        let expected = 2858;
+        let result = Day9::solve_part2(SAMPLE_TEXT).unwrap();
+        assert_eq!(expected, result);
+    }
+
+    #[test]
+    fn expand_small_map() {
+        let disk = Day9::parse("12345").unwrap();
+        assert_eq!(render(&disk.blocks()), "0..111....22222");
+    }
+
+    #[test]
+    fn compact_small_map() {
+        let mut blocks = Day9::parse("12345").unwrap().blocks();
+        compact_blocks(&mut blocks);
+        assert_eq!(render(&blocks), "022111222......");
+    }
+
+    #[test]
+    fn whole_files_move_left_only() {
+        let disk = Day9::parse(SAMPLE_TEXT).unwrap();
+        let files = compact_files(&disk);
+        let mut blocks = vec![None; disk.blocks().len()];
+        for (id, file) in files.iter().enumerate() {
+            blocks[file.start..file.start + file.len].fill(Some(id));
+        }
+        assert_eq!(
+            render(&blocks),
+            "00992111777.44.333....5555.6666.....8888.."
+        );
+    }
+
+    #[test]
+    fn empty_file_joins_gaps() {
+        // The gaps around file 1 (which is empty) make room for file 2
+        let disk = Day9::parse("11023").unwrap();
+        let files = compact_files(&disk);
+        assert_eq!(files[2], Span { start: 1, len: 3 });
+    }
+
+    #[test]
+    fn non_digit_is_reported() {
+        let error = Day9::parse("2333x33").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 9 input, line 1, column 5: unexpected character `x`"
+        );
+    }
+}
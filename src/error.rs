@@ -0,0 +1,186 @@
+use std::{fmt, io, str::FromStr};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/*
+Everything that can go wrong between reading the input and printing an answer.
+ */
+#[derive(Debug)]
+pub enum Error {
+    // The input couldn't be read at all
+    Input(io::Error),
+    // The input was read, but doesn't look like that day's puzzle
+    Parse(ParseError),
+    // The input parsed fine, but the puzzle has no answer for it
+    NoSolution { day: u8, part: u8, reason: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Input(e) => write!(f, "{e}"),
+            Error::Parse(e) => write!(f, "{e}"),
+            Error::NoSolution { day, part, reason } => {
+                write!(f, "day {day} part {part} has no solution: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(value: io::Error) -> Self {
+        Error::Input(value)
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(value: ParseError) -> Self {
+        Error::Parse(value)
+    }
+}
+
+/*
+A spot in the input that a parser couldn't make sense of.
+
+`line` and `column` are 1-based, like an editor shows them. `text` is the
+offending piece of input, which may be empty when something is missing.
+ */
+#[derive(Debug, PartialEq)]
+pub struct ParseError {
+    pub day: u8,
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub kind: ParseErrorKind,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ParseErrorKind {
+    // Expected a number here
+    InvalidNumber,
+    // The line ended before this field showed up
+    MissingField(&'static str),
+    // A character that isn't part of the puzzle's alphabet
+    UnexpectedChar,
+    // A grid line that isn't as wide as the first one
+    LineLength { expected: usize },
+    // A whole block of the input is absent (e.g.: day 5's page updates)
+    MissingSection(&'static str),
+}
+
+impl ParseError {
+    /*
+    Build an error for `token`, which must be a slice of `line`. The column is
+    worked out from where `token` sits inside `line`.
+
+    `line_idx` is 0-based, as it comes out of `enumerate()`.
+     */
+    pub fn in_line(
+        day: u8,
+        line_idx: usize,
+        line: &str,
+        token: &str,
+        kind: ParseErrorKind,
+    ) -> Self {
+        let offset = (token.as_ptr() as usize).saturating_sub(line.as_ptr() as usize);
+        let offset = offset.min(line.len());
+        Self {
+            day,
+            line: line_idx + 1,
+            column: line[..offset].chars().count() + 1,
+            text: token.to_string(),
+            kind,
+        }
+    }
+
+    // Error for something missing at the end of a line.
+    pub fn end_of_line(day: u8, line_idx: usize, line: &str, kind: ParseErrorKind) -> Self {
+        Self::in_line(day, line_idx, line, &line[line.len()..], kind)
+    }
+}
+
+/*
+Parse `token` (a slice of `line`) as a number, or point at it in a ParseError.
+ */
+pub fn parse_number<T: FromStr>(
+    day: u8,
+    line_idx: usize,
+    line: &str,
+    token: &str,
+) -> std::result::Result<T, ParseError> {
+    token
+        .parse::<T>()
+        .map_err(|_| ParseError::in_line(day, line_idx, line, token, ParseErrorKind::InvalidNumber))
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "day {} input, line {}, column {}: ",
+            self.day, self.line, self.column
+        )?;
+        match &self.kind {
+            ParseErrorKind::InvalidNumber => write!(f, "expected a number, found `{}`", self.text),
+            ParseErrorKind::MissingField(field) => write!(f, "missing {field}"),
+            ParseErrorKind::UnexpectedChar => write!(f, "unexpected character `{}`", self.text),
+            ParseErrorKind::LineLength { expected } => write!(
+                f,
+                "line is {} characters long, expected {expected}",
+                self.text.chars().count()
+            ),
+            ParseErrorKind::MissingSection(section) => write!(f, "missing {section}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn column_from_token_position() {
+        let line = "12   x4";
+        let token = &line[5..];
+        let error = ParseError::in_line(1, 2, line, token, ParseErrorKind::InvalidNumber);
+        assert_eq!(error.line, 3);
+        assert_eq!(error.column, 6);
+        assert_eq!(error.text, "x4");
+    }
+
+    #[test]
+    fn display_points_at_the_problem() {
+        let line = "..#.X";
+        let error = ParseError::in_line(6, 0, line, &line[4..], ParseErrorKind::UnexpectedChar);
+        assert_eq!(
+            error.to_string(),
+            "day 6 input, line 1, column 5: unexpected character `X`"
+        );
+    }
+
+    #[test]
+    fn bad_number_is_located() {
+        let line = "7 6 4 2x 1";
+        let result = parse_number::<i32>(2, 4, line, &line[6..8]);
+        let expected = ParseError {
+            day: 2,
+            line: 5,
+            column: 7,
+            text: "2x".to_string(),
+            kind: ParseErrorKind::InvalidNumber,
+        };
+        assert_eq!(result, Err(expected));
+    }
+
+    #[test]
+    fn missing_field_at_end_of_line() {
+        let error = ParseError::end_of_line(1, 0, "123", ParseErrorKind::MissingField("number 2"));
+        assert_eq!(error.column, 4);
+        assert_eq!(
+            error.to_string(),
+            "day 1 input, line 1, column 4: missing number 2"
+        );
+    }
+}
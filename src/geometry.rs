@@ -0,0 +1,330 @@
+// Integer 2D points and vectors, plus the 4- and 8-way compass directions.
+
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+/*
+Screen-style 2D coordinates: x grows to the right and y grows downwards, the
+same way `Grid` lays out its cells.
+
+A Point is a place and a Vector is a displacement between two places. Points
+can't be added together, but `Point - Point` gives the Vector between them and
+`Point + Vector` moves a Point.
+ */
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vector {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    #[allow(dead_code)]
+    pub fn manhattan_distance(self, other: Point) -> usize {
+        (other - self).manhattan_length()
+    }
+
+    #[allow(dead_code)]
+    pub fn chebyshev_distance(self, other: Point) -> usize {
+        (other - self).chebyshev_length()
+    }
+
+    // The 4 orthogonally adjacent points. These may be off the edge of a grid!
+    pub fn neighbours4(self) -> impl Iterator<Item = Point> {
+        Dir4::ALL.into_iter().map(move |dir| self + dir.as_vector())
+    }
+
+    // All 8 surrounding points. These may be off the edge of a grid!
+    #[allow(dead_code)]
+    pub fn neighbours8(self) -> impl Iterator<Item = Point> {
+        Dir8::ALL.into_iter().map(move |dir| self + dir.as_vector())
+    }
+}
+
+impl Vector {
+    pub const fn new(x: isize, y: isize) -> Self {
+        Self { x, y }
+    }
+
+    pub fn manhattan_length(self) -> usize {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+
+    #[allow(dead_code)]
+    pub fn chebyshev_length(self) -> usize {
+        self.x.unsigned_abs().max(self.y.unsigned_abs())
+    }
+
+    // Quarter turn clockwise, as it looks on screen (y pointing down).
+    #[allow(dead_code)]
+    pub const fn rotate_cw(self) -> Self {
+        Self::new(-self.y, self.x)
+    }
+
+    // Quarter turn counter-clockwise, as it looks on screen (y pointing down).
+    #[allow(dead_code)]
+    pub const fn rotate_ccw(self) -> Self {
+        Self::new(self.y, -self.x)
+    }
+
+    /*
+    The smallest whole-number step in the same direction, found by dividing out
+    the gcd of the components. E.g.: (4, -6) reduces to (2, -3).
+
+    Stepping by the reduced vector visits every grid point on the line, where
+    stepping by the original would skip some. The zero vector stays as it is.
+     */
+    pub fn reduced(self) -> Self {
+        let divisor = gcd(self.x.unsigned_abs(), self.y.unsigned_abs()) as isize;
+        if divisor == 0 {
+            self
+        } else {
+            Self::new(self.x / divisor, self.y / divisor)
+        }
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl From<(isize, isize)> for Point {
+    fn from(value: (isize, isize)) -> Self {
+        Self::new(value.0, value.1)
+    }
+}
+
+impl From<(isize, isize)> for Vector {
+    fn from(value: (isize, isize)) -> Self {
+        Self::new(value.0, value.1)
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Vector::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Self;
+
+    fn add(self, rhs: Vector) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Self;
+
+    fn sub(self, rhs: Vector) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Add for Vector {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<isize> for Vector {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+// The 4 orthogonal directions, counter-clockwise starting from the right.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir4 {
+    Right,
+    Up,
+    Left,
+    Down,
+}
+
+impl Dir4 {
+    pub const ALL: [Dir4; 4] = [Dir4::Right, Dir4::Up, Dir4::Left, Dir4::Down];
+
+    // returns the unit vector for the named direction
+    pub const fn as_vector(self) -> Vector {
+        match self {
+            Dir4::Right => Vector::new(1, 0),
+            Dir4::Up => Vector::new(0, -1),
+            Dir4::Left => Vector::new(-1, 0),
+            Dir4::Down => Vector::new(0, 1),
+        }
+    }
+
+    pub const fn clockwise(self) -> Self {
+        match self {
+            Dir4::Right => Dir4::Down,
+            Dir4::Up => Dir4::Right,
+            Dir4::Left => Dir4::Up,
+            Dir4::Down => Dir4::Left,
+        }
+    }
+
+    pub const fn counter_clockwise(self) -> Self {
+        match self {
+            Dir4::Right => Dir4::Up,
+            Dir4::Up => Dir4::Left,
+            Dir4::Left => Dir4::Down,
+            Dir4::Down => Dir4::Right,
+        }
+    }
+
+    #[allow(dead_code)]
+    pub const fn opposite(self) -> Self {
+        match self {
+            Dir4::Right => Dir4::Left,
+            Dir4::Up => Dir4::Down,
+            Dir4::Left => Dir4::Right,
+            Dir4::Down => Dir4::Up,
+        }
+    }
+}
+
+// The 8 compass directions, counter-clockwise starting from the right.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Dir8 {
+    Right,
+    UpRight,
+    Up,
+    UpLeft,
+    Left,
+    DownLeft,
+    Down,
+    DownRight,
+}
+
+impl Dir8 {
+    pub const ALL: [Dir8; 8] = [
+        Dir8::Right,
+        Dir8::UpRight,
+        Dir8::Up,
+        Dir8::UpLeft,
+        Dir8::Left,
+        Dir8::DownLeft,
+        Dir8::Down,
+        Dir8::DownRight,
+    ];
+
+    // returns the unit (or diagonal unit) vector for the named direction
+    pub const fn as_vector(self) -> Vector {
+        match self {
+            Dir8::Right => Vector::new(1, 0),
+            Dir8::UpRight => Vector::new(1, -1),
+            Dir8::Up => Vector::new(0, -1),
+            Dir8::UpLeft => Vector::new(-1, -1),
+            Dir8::Left => Vector::new(-1, 0),
+            Dir8::DownLeft => Vector::new(-1, 1),
+            Dir8::Down => Vector::new(0, 1),
+            Dir8::DownRight => Vector::new(1, 1),
+        }
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(value: Dir4) -> Self {
+        match value {
+            Dir4::Right => Dir8::Right,
+            Dir4::Up => Dir8::Up,
+            Dir4::Left => Dir8::Left,
+            Dir4::Down => Dir8::Down,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn point_arithmetic() {
+        let a = Point::new(2, 3);
+        let b = Point::new(5, 1);
+        let delta = b - a;
+        assert_eq!(delta, Vector::new(3, -2));
+        assert_eq!(a + delta, b);
+        assert_eq!(b + delta * 2, Point::new(11, -3));
+        assert_eq!(b - delta, a);
+    }
+
+    #[test]
+    fn distances() {
+        let a = Point::new(-1, 4);
+        let b = Point::new(3, 1);
+        assert_eq!(a.manhattan_distance(b), 7);
+        assert_eq!(a.chebyshev_distance(b), 4);
+    }
+
+    #[test]
+    fn rotation_matches_directions() {
+        for dir in Dir4::ALL {
+            assert_eq!(dir.as_vector().rotate_cw(), dir.clockwise().as_vector());
+            assert_eq!(
+                dir.as_vector().rotate_ccw(),
+                dir.counter_clockwise().as_vector()
+            );
+            assert_eq!(-dir.as_vector(), dir.opposite().as_vector());
+        }
+    }
+
+    #[test]
+    fn reduce_by_gcd() {
+        assert_eq!(Vector::new(4, -6).reduced(), Vector::new(2, -3));
+        assert_eq!(Vector::new(0, 5).reduced(), Vector::new(0, 1));
+        assert_eq!(Vector::new(-3, 0).reduced(), Vector::new(-1, 0));
+        assert_eq!(Vector::new(0, 0).reduced(), Vector::new(0, 0));
+    }
+
+    #[test]
+    fn neighbour_counts() {
+        let origin = Point::new(0, 0);
+        assert_eq!(origin.neighbours4().count(), 4);
+        assert!(origin
+            .neighbours8()
+            .all(|p| origin.chebyshev_distance(p) == 1));
+    }
+}
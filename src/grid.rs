@@ -0,0 +1,284 @@
+use std::fmt::{self, Display};
+
+use crate::{
+    error::{ParseError, ParseErrorKind},
+    geometry::Point,
+};
+
+/*
+A rectangular grid of cells stored row-major, addressed by (x, y) with (0, 0)
+in the top-left corner. x grows to the right and y grows downwards.
+
+Coordinates are `isize` so callers can step off the edge (e.g.: `x - 1`) and
+let the bounds check sort it out. Anything out of bounds is `None`.
+ */
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grid<T> {
+    width: isize,
+    height: isize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /*
+    Parse a block of text into a grid, one cell per character.
+
+    `cell` maps each character to a cell, returning `None` for characters that
+    don't belong in this puzzle. Those, and lines that aren't as wide as the
+    first one, are reported as ParseErrors against `day`'s input.
+     */
+    pub fn parse(
+        day: u8,
+        text: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let width = text.lines().next().map_or(0, |line| line.chars().count());
+        let mut cells = Vec::with_capacity(text.len());
+        let mut height = 0;
+        for (line_idx, line) in text.lines().enumerate() {
+            if line.chars().count() != width {
+                let kind = ParseErrorKind::LineLength { expected: width };
+                return Err(ParseError::in_line(day, line_idx, line, line, kind));
+            }
+            for (idx, c) in line.char_indices() {
+                match cell(c) {
+                    Some(value) => cells.push(value),
+                    None => {
+                        let offending = &line[idx..idx + c.len_utf8()];
+                        let kind = ParseErrorKind::UnexpectedChar;
+                        return Err(ParseError::in_line(day, line_idx, line, offending, kind));
+                    }
+                }
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width as isize,
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> isize {
+        self.width
+    }
+
+    pub fn height(&self) -> isize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, x: isize, y: isize) -> bool {
+        x >= 0 && x < self.width && y >= 0 && y < self.height
+    }
+
+    fn index_of(&self, x: isize, y: isize) -> Option<usize> {
+        if self.in_bounds(x, y) {
+            Some((x + y * self.width) as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        self.index_of(x, y).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        self.index_of(x, y).map(|idx| &mut self.cells[idx])
+    }
+
+    // `get()`, for a Point
+    pub fn at(&self, p: Point) -> Option<&T> {
+        self.get(p.x, p.y)
+    }
+
+    // `get_mut()`, for a Point
+    pub fn at_mut(&mut self, p: Point) -> Option<&mut T> {
+        self.get_mut(p.x, p.y)
+    }
+
+    // Every coordinate in the grid, row by row.
+    pub fn coords(&self) -> impl Iterator<Item = (isize, isize)> + Clone + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    // `coords()`, as Points
+    pub fn points(&self) -> impl Iterator<Item = Point> + Clone + use<T> {
+        self.coords().map(Point::from)
+    }
+
+    // Every cell in the grid, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    // Coordinates of the first cell (row by row) matching `predicate`.
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<(isize, isize)> {
+        let idx = self.cells.iter().position(predicate)? as isize;
+        Some((idx % self.width, idx / self.width))
+    }
+
+    // The in-bounds orthogonal neighbours of `p`
+    pub fn neighbours4(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        p.neighbours4().filter(|n| self.in_bounds(n.x, n.y))
+    }
+
+    // The in-bounds orthogonal and diagonal neighbours of `p`
+    #[allow(dead_code)]
+    pub fn neighbours8(&self, p: Point) -> impl Iterator<Item = Point> + '_ {
+        p.neighbours8().filter(|n| self.in_bounds(n.x, n.y))
+    }
+
+    /*
+    Walk from (x, y) in steps of (dx, dy), yielding cells until leaving the grid.
+    The starting cell is included.
+     */
+    pub fn ray(&self, x: isize, y: isize, dx: isize, dy: isize) -> impl Iterator<Item = &T> {
+        (0..)
+            .map(move |step| (x + dx * step, y + dy * step))
+            .map_while(|(x, y)| self.get(x, y))
+    }
+
+    pub fn row(&self, y: isize) -> impl Iterator<Item = &T> {
+        self.ray(0, y, 1, 0)
+    }
+
+    #[allow(dead_code)]
+    pub fn column(&self, x: isize) -> impl Iterator<Item = &T> {
+        self.ray(x, 0, 0, 1)
+    }
+
+    // Down and to the right, starting from (x, y)
+    #[allow(dead_code)]
+    pub fn diagonal(&self, x: isize, y: isize) -> impl Iterator<Item = &T> {
+        self.ray(x, y, 1, 1)
+    }
+
+    // Down and to the left, starting from (x, y)
+    #[allow(dead_code)]
+    pub fn anti_diagonal(&self, x: isize, y: isize) -> impl Iterator<Item = &T> {
+        self.ray(x, y, -1, 1)
+    }
+
+    // A new grid of the same size, with `f` applied to every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: isize, height: isize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; (width * height) as usize],
+        }
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in self.row(y) {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SAMPLE_TEXT: &str = "abc
+def
+ghi
+jkl";
+
+    fn letters() -> Grid<char> {
+        Grid::parse(0, SAMPLE_TEXT, Some).unwrap()
+    }
+
+    #[test]
+    fn parse_dimensions() {
+        let grid = letters();
+        assert_eq!((grid.width(), grid.height()), (3, 4));
+        assert_eq!(grid.get(2, 1), Some(&'f'));
+    }
+
+    #[test]
+    fn out_of_bounds_is_none() {
+        let grid = letters();
+        assert_eq!(grid.get(-1, 0), None);
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 4), None);
+    }
+
+    #[test]
+    fn parse_rejects_unknown_cells() {
+        let error = Grid::parse(6, "..#\n.x.", |c| match c {
+            '.' | '#' => Some(c),
+            _ => None,
+        })
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 6 input, line 2, column 2: unexpected character `x`"
+        );
+    }
+
+    #[test]
+    fn parse_rejects_ragged_lines() {
+        let error = Grid::parse(4, "abc\nde\nfgh", Some).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "day 4 input, line 2, column 1: line is 2 characters long, expected 3"
+        );
+    }
+
+    #[test]
+    fn lines_and_rays() {
+        let grid = letters();
+        assert_eq!(grid.row(1).collect::<String>(), "def");
+        assert_eq!(grid.column(2).collect::<String>(), "cfil");
+        assert_eq!(grid.diagonal(0, 1).collect::<String>(), "dhl");
+        assert_eq!(grid.anti_diagonal(2, 0).collect::<String>(), "ceg");
+    }
+
+    #[test]
+    fn neighbours_stay_in_bounds() {
+        let grid = letters();
+        let corner: Vec<_> = grid.neighbours4(Point::new(0, 0)).collect();
+        assert_eq!(corner, vec![Point::new(1, 0), Point::new(0, 1)]);
+        assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8(Point::new(2, 3)).count(), 3);
+    }
+
+    #[test]
+    fn map_and_display() {
+        let upper = letters().map(|c| c.to_ascii_uppercase());
+        assert_eq!(upper.to_string(), "ABC\nDEF\nGHI\nJKL");
+    }
+
+    #[test]
+    fn get_by_point() {
+        let grid = letters();
+        assert_eq!(grid.at(Point::new(1, 3)), Some(&'k'));
+        assert_eq!(grid.at(Point::new(1, -1)), None);
+    }
+
+    #[test]
+    fn position_of_cell() {
+        assert_eq!(letters().position(|&c| c == 'h'), Some((1, 2)));
+    }
+}
@@ -0,0 +1,168 @@
+use std::{
+    borrow::Cow,
+    fmt,
+    io::{self, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    error::{Error, Result},
+    input_constants,
+};
+
+// Directory searched for `dayNN.txt` files when no explicit input is given.
+pub const INPUT_DIR: &str = "inputs";
+
+/*
+Where a day's puzzle input comes from.
+
+`resolve()` picks one in priority order:
+    1. An explicit path from the command line (`-` means stdin)
+    2. `inputs/dayNN.txt`, if it exists
+    3. The constants embedded in `input_constants`
+ */
+#[derive(Debug, PartialEq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+    Embedded(u8),
+}
+
+impl InputSource {
+    pub fn resolve(day: u8, explicit: Option<&str>) -> Self {
+        match explicit {
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(PathBuf::from(path)),
+            None => {
+                let default_file = default_path(INPUT_DIR, day);
+                if default_file.is_file() {
+                    InputSource::File(default_file)
+                } else {
+                    InputSource::Embedded(day)
+                }
+            }
+        }
+    }
+
+    pub fn load(&self) -> Result<Cow<'static, str>> {
+        match self {
+            InputSource::File(path) => Ok(Cow::Owned(std::fs::read_to_string(path)?)),
+            InputSource::Stdin => {
+                let mut text = String::new();
+                io::stdin().read_to_string(&mut text)?;
+                Ok(Cow::Owned(text))
+            }
+            InputSource::Embedded(day) => embedded(*day).map(Cow::Borrowed).ok_or_else(|| {
+                Error::Input(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!(
+                        "no embedded input for day {day}; save it as {}",
+                        default_path(INPUT_DIR, *day).display()
+                    ),
+                ))
+            }),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::File(path) => write!(f, "{}", path.display()),
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::Embedded(day) => write!(f, "embedded input for day {day}"),
+        }
+    }
+}
+
+/*
+Clean up puzzle text the way it shows up in real files.
+
+Windows line endings become plain '\n', and trailing newlines are dropped so the
+last line of the puzzle really is the last line. Text that's already clean is
+passed through without copying.
+ */
+pub fn normalize(text: &str) -> Cow<'_, str> {
+    let text = if text.contains('\r') {
+        Cow::Owned(text.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(text)
+    };
+
+    match text {
+        Cow::Borrowed(text) => Cow::Borrowed(text.trim_end_matches('\n')),
+        Cow::Owned(text) => Cow::Owned(text.trim_end_matches('\n').to_string()),
+    }
+}
+
+// e.g.: `inputs/day08.txt`
+pub fn default_path(dir: impl AsRef<Path>, day: u8) -> PathBuf {
+    dir.as_ref().join(format!("day{day:02}.txt"))
+}
+
+// The inputs baked into the binary, if we have one for this day.
+pub fn embedded(day: u8) -> Option<&'static str> {
+    match day {
+        1 => Some(input_constants::DAY1),
+        2 => Some(input_constants::DAY2),
+        3 => Some(input_constants::DAY3),
+        4 => Some(input_constants::DAY4),
+        5 => Some(input_constants::DAY5),
+        6 => Some(input_constants::DAY6),
+        8 => Some(input_constants::DAY8),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn default_path_is_zero_padded() {
+        assert_eq!(default_path("inputs", 8), PathBuf::from("inputs/day08.txt"));
+        assert_eq!(
+            default_path("inputs", 23),
+            PathBuf::from("inputs/day23.txt")
+        );
+    }
+
+    #[test]
+    fn normalize_clean_text_is_untouched() {
+        let text = "ab\ncd";
+        assert!(matches!(normalize(text), Cow::Borrowed("ab\ncd")));
+    }
+
+    #[test]
+    fn normalize_trailing_newlines() {
+        assert_eq!(normalize("ab\ncd\n"), "ab\ncd");
+        assert_eq!(normalize("ab\ncd\n\n"), "ab\ncd");
+    }
+
+    #[test]
+    fn normalize_crlf() {
+        assert_eq!(normalize("ab\r\ncd\r\n"), "ab\ncd");
+        assert_eq!(normalize("ab\r\n\r\ncd"), "ab\n\ncd");
+    }
+
+    #[test]
+    fn explicit_input_wins() {
+        assert_eq!(InputSource::resolve(1, Some("-")), InputSource::Stdin);
+        assert_eq!(
+            InputSource::resolve(1, Some("mine.txt")),
+            InputSource::File(PathBuf::from("mine.txt"))
+        );
+    }
+
+    #[test]
+    fn missing_embedded_input_is_an_error() {
+        let result = InputSource::Embedded(7).load();
+        assert!(matches!(result, Err(Error::Input(e)) if e.kind() == io::ErrorKind::NotFound));
+    }
+
+    #[test]
+    fn embedded_input_is_borrowed() {
+        let text = InputSource::Embedded(1).load().unwrap();
+        assert!(matches!(text, Cow::Borrowed(_)));
+    }
+}
@@ -1,67 +1,197 @@
+mod cli;
 mod day1;
+mod day10;
+mod day11;
+mod day12;
+mod day13;
+mod day14;
+mod day15;
+mod day16;
+mod day17;
+mod day18;
+mod day19;
+mod day20;
+mod day21;
+mod day22;
+mod day23;
 mod day2;
 mod day3;
 mod day4;
 mod day5;
 mod day6;
+mod day7;
 mod day8;
+mod day9;
+mod error;
+mod geometry;
+mod grid;
+mod input;
 mod input_constants;
+mod solution;
 
-use day1::{process_d1p1, process_d1p2};
-use day2::{process_d2p1, process_d2p2};
-use day3::{process_d3p1, process_d3p2};
-use day4::{process_d4p1, process_d4p2};
-use day5::process_d5p1;
-use day6::{process_d6p1, process_d6p2};
-use day8::{process_d8p1, process_d8p2};
+use std::process::ExitCode;
 
-fn main() {
-    println!(
-        "Day 1 Part 1 result: {}",
-        process_d1p1(input_constants::DAY1)
-    );
-    println!(
-        "Day 1 Part 2 result: {}",
-        process_d1p2(input_constants::DAY1)
-    );
-    println!(
-        "Day 2 Part 1 result: {}",
-        process_d2p1(input_constants::DAY2)
-    );
-    println!(
-        "Day 2 Part 2 result: {}",
-        process_d2p2(input_constants::DAY2)
-    );
-    println!(
-        "Day 3 Part 1 result: {}",
-        process_d3p1(input_constants::DAY3)
-    );
-    println!(
-        "Day 3 Part 2 result: {}",
-        process_d3p2(input_constants::DAY3)
-    );
-    println!(
-        "Day 4 Part 1 Result: {}",
-        process_d4p1(input_constants::DAY4)
-    );
-    println!(
-        "Day 4 Part 2 Result: {}",
-        process_d4p2(input_constants::DAY4)
-    );
-    println!(
-        "Day 5 Part 1 Result: {}",
-        process_d5p1(input_constants::DAY5)
-    );
-    println!(
-        "Day 6 Part 1 Result: {}",
-        process_d6p1(input_constants::DAY6)
-    );
-    println!(
-        "Day 8 Part 1 Result: {}",
-        process_d8p1(input_constants::DAY8)
-    );
-    println!(
-        "Day 8 Part 2 Result: {}",
-        process_d8p2(input_constants::DAY8)
-    );
+use cli::{Command, Selection};
+use day1::Day1;
+use day10::Day10;
+use day11::Day11;
+use day12::Day12;
+use day13::Day13;
+use day14::Day14;
+use day15::Day15;
+use day16::Day16;
+use day17::Day17;
+use day18::Day18;
+use day19::Day19;
+use day20::Day20;
+use day21::Day21;
+use day22::Day22;
+use day23::Day23;
+use day2::Day2;
+use day3::Day3;
+use day4::Day4;
+use day5::Day5;
+use day6::Day6;
+use day7::Day7;
+use day8::Day8;
+use day9::Day9;
+use input::InputSource;
+use solution::Day;
+
+// Every day with a Solution, in the order `run --all` prints them.
+const DAYS: &[Day] = &[
+    Day::of::<Day1>(),
+    Day::of::<Day2>(),
+    Day::of::<Day3>(),
+    Day::of::<Day4>(),
+    Day::of::<Day5>(),
+    Day::of::<Day6>(),
+    Day::of::<Day7>(),
+    Day::of::<Day8>(),
+    Day::of::<Day9>(),
+    Day::of::<Day10>(),
+    Day::of::<Day11>(),
+    Day::of::<Day12>(),
+    Day::of::<Day13>(),
+    Day::of::<Day14>(),
+    Day::of::<Day15>(),
+    Day::of::<Day16>(),
+    Day::of::<Day17>(),
+    Day::of::<Day18>(),
+    Day::of::<Day19>(),
+    Day::of::<Day20>(),
+    Day::of::<Day21>(),
+    Day::of::<Day22>(),
+    Day::of::<Day23>(),
+];
+
+fn main() -> ExitCode {
+    let command = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(command) => command,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{}", cli::USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match command {
+        Command::Help => println!("{}", cli::USAGE),
+        Command::List => {
+            for day in DAYS {
+                println!("Day {}", day.day);
+            }
+        }
+        Command::Run(selection) => return run(&selection),
+    }
+    ExitCode::SUCCESS
+}
+
+fn run(selection: &Selection) -> ExitCode {
+    let (explicit_input, part, verbose) = match selection {
+        Selection::Day {
+            input,
+            part,
+            verbose,
+            ..
+        } => (input.as_deref(), *part, *verbose),
+        Selection::All => (None, None, false),
+    };
+
+    let selected: Vec<&Day> = DAYS
+        .iter()
+        .filter(|day| match selection {
+            Selection::All => true,
+            Selection::Day { day: wanted, .. } => day.day == *wanted,
+        })
+        .collect();
+
+    if selected.is_empty() {
+        eprintln!("error: no solver for the selected day. Try `aoc2024 list`.");
+        return ExitCode::FAILURE;
+    }
+
+    for day in selected {
+        let source = InputSource::resolve(day.day, explicit_input);
+        if skip_missing(selection, &source) {
+            eprintln!(
+                "note: skipping day {}: no input; save it as {}",
+                day.day,
+                input::default_path(input::INPUT_DIR, day.day).display()
+            );
+            continue;
+        }
+        let input = match source.load() {
+            Ok(text) => text,
+            Err(e) => {
+                eprintln!("error: couldn't read {source}: {e}");
+                return ExitCode::FAILURE;
+            }
+        };
+
+        match (day.run)(&input, part, verbose) {
+            Ok(answers) => {
+                for (part, answer) in answers {
+                    println!("Day {} Part {} Result: {}", day.day, part, answer);
+                }
+            }
+            Err(e) => {
+                eprintln!("error: {e}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/*
+`run --all` skips a day with no input file and nothing embedded, so one missing
+input doesn't stop every later day. Asking for that day with `--day` still fails.
+ */
+fn skip_missing(selection: &Selection, source: &InputSource) -> bool {
+    match (selection, source) {
+        (Selection::All, InputSource::Embedded(day)) => input::embedded(*day).is_none(),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn all_skips_days_without_input() {
+        assert!(skip_missing(&Selection::All, &InputSource::Embedded(7)));
+        assert!(!skip_missing(&Selection::All, &InputSource::Embedded(1)));
+    }
+
+    #[test]
+    fn chosen_day_without_input_is_not_skipped() {
+        let selection = Selection::Day {
+            day: 7,
+            part: None,
+            input: None,
+            verbose: false,
+        };
+        assert!(!skip_missing(&selection, &InputSource::Embedded(7)));
+    }
 }
@@ -0,0 +1,152 @@
+use std::fmt::Display;
+
+use crate::{error::Result, input::normalize};
+
+/*
+A single day's puzzle.
+
+The input is parsed once, and both parts work from the parsed form. Each part
+has its own answer type since they don't always agree (or even stay numbers).
+Malformed input is reported as an `Error` instead of panicking.
+
+`parse` always receives normalized text (see `input::normalize`): '\n' line
+endings and no trailing newline.
+ */
+pub trait Solution {
+    const DAY: u8;
+
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+
+    // Parse-and-solve shortcuts for the tests. The runner parses once for both parts.
+    #[cfg(test)]
+    fn solve_part1(input: &str) -> Result<Self::Answer1> {
+        Self::part1(&Self::parse(&normalize(input))?)
+    }
+
+    #[cfg(test)]
+    fn solve_part2(input: &str) -> Result<Self::Answer2> {
+        Self::part2(&Self::parse(&normalize(input))?)
+    }
+}
+
+/*
+Type-erased handle on a Solution so every day can sit in one registry.
+
+`run` parses the input and solves the requested part (or both, for `None`),
+returning each answer rendered as text alongside its part number. The first
+error stops the run. `verbose` renders answers in their alternate form
+(`{:#}`), for answers that have more to show than the number itself.
+ */
+pub struct Day {
+    pub day: u8,
+    pub run: fn(&str, Option<u8>, bool) -> Result<Vec<PartAnswer>>,
+}
+
+// (part number, rendered answer)
+pub type PartAnswer = (u8, String);
+
+impl Day {
+    pub const fn of<S: Solution>() -> Self {
+        Self {
+            day: S::DAY,
+            run: run_parts::<S>,
+        }
+    }
+}
+
+fn run_parts<S: Solution>(input: &str, part: Option<u8>, verbose: bool) -> Result<Vec<PartAnswer>> {
+    let parsed = S::parse(&normalize(input))?;
+    let render = |answer: &dyn Display| {
+        if verbose {
+            format!("{answer:#}")
+        } else {
+            answer.to_string()
+        }
+    };
+    let mut answers = Vec::new();
+    if part.is_none() || part == Some(1) {
+        answers.push((1, render(&S::part1(&parsed)?)));
+    }
+    if part.is_none() || part == Some(2) {
+        answers.push((2, render(&S::part2(&parsed)?)));
+    }
+    Ok(answers)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Counts the characters of the input, then the lines.
+    struct CharCount;
+
+    // The line count, with its unit in the alternate form.
+    struct Lines(usize);
+
+    impl Display for Lines {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.0)?;
+            if f.alternate() {
+                write!(f, " lines")?;
+            }
+            Ok(())
+        }
+    }
+
+    impl Solution for CharCount {
+        const DAY: u8 = 0;
+        type Input = String;
+        type Answer1 = usize;
+        type Answer2 = Lines;
+
+        fn parse(input: &str) -> Result<Self::Input> {
+            Ok(input.to_string())
+        }
+
+        fn part1(input: &Self::Input) -> Result<Self::Answer1> {
+            Ok(input.chars().count())
+        }
+
+        fn part2(input: &Self::Input) -> Result<Self::Answer2> {
+            Ok(Lines(input.lines().count()))
+        }
+    }
+
+    #[test]
+    fn run_both_parts() {
+        let day = Day::of::<CharCount>();
+        let expected = vec![(1, "5".to_string()), (2, "1".to_string())];
+        assert_eq!((day.run)("hello", None, false).unwrap(), expected);
+    }
+
+    #[test]
+    fn input_is_normalized() {
+        let day = Day::of::<CharCount>();
+        let answers = (day.run)("ab\r\ncd\r\n", Some(1), false).unwrap();
+        assert_eq!(answers, vec![(1, "5".to_string())]);
+    }
+
+    #[test]
+    fn run_one_part() {
+        let day = Day::of::<CharCount>();
+        assert_eq!(
+            (day.run)("hello", Some(1), false).unwrap(),
+            vec![(1, "5".to_string())]
+        );
+    }
+
+    #[test]
+    fn verbose_uses_alternate_form() {
+        let day = Day::of::<CharCount>();
+        let expected = vec![(1, "11".to_string()), (2, "2 lines".to_string())];
+        assert_eq!((day.run)("hello\nworld", None, true).unwrap(), expected);
+    }
+}